@@ -0,0 +1,146 @@
+use std::alloc::{self, Layout};
+use std::cell::{Cell, RefCell};
+use std::ptr::{self, NonNull};
+
+const ARENA_MIN_CHUNK_SIZE: usize = 4096;
+
+/// # Safety
+///
+/// Memory returned by `allocate` must be valid for `layout` and stay valid
+/// until it is passed back to `deallocate` on the same allocator.
+pub unsafe trait Allocator {
+  fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+  /// # Safety
+  ///
+  /// `pointer` must have been returned by `allocate` on this allocator with the same `layout`.
+  unsafe fn deallocate(&self, pointer: NonNull<u8>, layout: Layout);
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+  fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+    if layout.size() == 0 {
+      return Some(Self::dangling(layout));
+    }
+
+    NonNull::new(unsafe { alloc::alloc(layout) })
+  }
+
+  unsafe fn deallocate(&self, pointer: NonNull<u8>, layout: Layout) {
+    if layout.size() != 0 {
+      alloc::dealloc(pointer.as_ptr(), layout);
+    }
+  }
+}
+
+impl Global {
+  fn dangling(layout: Layout) -> NonNull<u8> {
+    unsafe { NonNull::new_unchecked(ptr::without_provenance_mut(layout.align())) }
+  }
+}
+
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+  fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+    (**self).allocate(layout)
+  }
+
+  unsafe fn deallocate(&self, pointer: NonNull<u8>, layout: Layout) {
+    (**self).deallocate(pointer, layout)
+  }
+}
+
+struct Chunk {
+  start: NonNull<u8>,
+  layout: Layout,
+}
+
+pub struct BumpArena {
+  chunks: RefCell<Vec<Chunk>>,
+  offset: Cell<usize>,
+  allocated_bytes: Cell<usize>,
+}
+
+impl BumpArena {
+  pub fn new() -> Self {
+    Self {
+      chunks: RefCell::new(Vec::new()),
+      offset: Cell::new(0),
+      allocated_bytes: Cell::new(0),
+    }
+  }
+
+  pub fn allocated_bytes(&self) -> usize {
+    self.allocated_bytes.get()
+  }
+
+  fn bump(&self, layout: Layout) -> Option<NonNull<u8>> {
+    let chunks = self.chunks.borrow();
+    let chunk = chunks.last()?;
+
+    let offset = self.offset.get();
+    let padding = unsafe { chunk.start.as_ptr().add(offset) }.align_offset(layout.align());
+    let start = offset.checked_add(padding)?;
+    let end = start.checked_add(layout.size())?;
+
+    if end > chunk.layout.size() {
+      return None;
+    }
+
+    self.offset.set(end);
+
+    NonNull::new(unsafe { chunk.start.as_ptr().add(start) })
+  }
+
+  fn allocate_chunk(&self, layout: Layout) -> Option<()> {
+    let size = layout.size().checked_add(layout.align())?.max(ARENA_MIN_CHUNK_SIZE);
+    let chunk_layout = Layout::from_size_align(size, 1).ok()?;
+    let start = NonNull::new(unsafe { alloc::alloc(chunk_layout) })?;
+
+    self.chunks.borrow_mut().push(Chunk { start, layout: chunk_layout });
+    self.offset.set(0);
+
+    Some(())
+  }
+}
+
+impl Default for BumpArena {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+unsafe impl Allocator for BumpArena {
+  fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+    if layout.size() == 0 {
+      return Some(Global::dangling(layout));
+    }
+
+    let pointer = match self.bump(layout) {
+      Some(pointer) => pointer,
+      None => {
+        self.allocate_chunk(layout)?;
+
+        self.bump(layout)?
+      }
+    };
+
+    self.allocated_bytes.set(self.allocated_bytes.get() + layout.size());
+
+    Some(pointer)
+  }
+
+  unsafe fn deallocate(&self, _pointer: NonNull<u8>, _layout: Layout) {}
+}
+
+impl Drop for BumpArena {
+  fn drop(&mut self) {
+    for chunk in self.chunks.get_mut().drain(..) {
+      unsafe {
+        alloc::dealloc(chunk.start.as_ptr(), chunk.layout);
+      }
+    }
+  }
+}
@@ -1,134 +1,1178 @@
-use std::alloc::{Layout, alloc, dealloc};
-use std::fmt::Display;
+use std::alloc::{Layout, handle_alloc_error};
+use std::fmt::{Debug, Display};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::ptr::{self, NonNull};
+use std::slice::{self, SliceIndex};
 
-type ArrayItem = u8;
+use crate::allocators::{Allocator, Global};
+use crate::errors::Error;
 
-const NOT_FOUND_INDEX: isize = -1;
 const ARRAY_QUARTER: usize = 4;
 
-pub struct DynamicArray {
-  items: *mut ArrayItem,
-  layout: Layout,
+pub trait GrowthPolicy {
+  fn grow(&self, capacity: usize, required: usize) -> usize;
+
+  fn shrink(&self, capacity: usize, length: usize) -> Option<usize> {
+    if length != 0 && capacity / length >= ARRAY_QUARTER {
+      Some(capacity - (capacity / 2))
+    } else {
+      None
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Doubling;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Factor(pub f64);
+
+#[derive(Clone, Copy, Debug)]
+pub struct FixedIncrement(pub usize);
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PowerOfTwo;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NeverShrink<P = Doubling>(pub P);
+
+impl GrowthPolicy for Doubling {
+  fn grow(&self, capacity: usize, required: usize) -> usize {
+    required.max(capacity.saturating_mul(2)).max(1)
+  }
+}
+
+impl GrowthPolicy for Factor {
+  fn grow(&self, capacity: usize, required: usize) -> usize {
+    let grown = (capacity as f64 * self.0).ceil() as usize;
+
+    required.max(grown).max(capacity + 1)
+  }
+}
+
+impl GrowthPolicy for FixedIncrement {
+  fn grow(&self, capacity: usize, required: usize) -> usize {
+    required.max(capacity.saturating_add(self.0.max(1)))
+  }
+}
+
+impl GrowthPolicy for PowerOfTwo {
+  fn grow(&self, _capacity: usize, required: usize) -> usize {
+    required.checked_next_power_of_two().unwrap_or(required)
+  }
+
+  fn shrink(&self, capacity: usize, length: usize) -> Option<usize> {
+    if length != 0 && capacity / length >= ARRAY_QUARTER {
+      Some(capacity / 2)
+    } else {
+      None
+    }
+  }
+}
+
+impl<P: GrowthPolicy> GrowthPolicy for NeverShrink<P> {
+  fn grow(&self, capacity: usize, required: usize) -> usize {
+    self.0.grow(capacity, required)
+  }
+
+  fn shrink(&self, _capacity: usize, _length: usize) -> Option<usize> {
+    None
+  }
+}
+
+pub struct DynamicArray<T, P: GrowthPolicy = Doubling, A: Allocator = Global> {
+  items: NonNull<T>,
   length: usize,
-  capacity: usize
+  capacity: usize,
+  policy: P,
+  allocator: A,
+  marker: PhantomData<T>,
 }
 
-impl DynamicArray {
+unsafe impl<T: Send, P: GrowthPolicy + Send, A: Allocator + Send> Send for DynamicArray<T, P, A> {}
+unsafe impl<T: Sync, P: GrowthPolicy + Sync, A: Allocator + Sync> Sync for DynamicArray<T, P, A> {}
+
+impl<T> DynamicArray<T> {
   pub fn new(size: usize) -> Self {
-    unsafe {
-      let (items, layout) = Self::allocate_unsafe(size);
+    Self::with_policy_in(size, Doubling, Global)
+  }
+}
+
+impl<T, P: GrowthPolicy> DynamicArray<T, P> {
+  pub fn with_policy(size: usize, policy: P) -> Self {
+    Self::with_policy_in(size, policy, Global)
+  }
+}
+
+impl<T, A: Allocator> DynamicArray<T, Doubling, A> {
+  pub fn new_in(size: usize, allocator: A) -> Self {
+    Self::with_policy_in(size, Doubling, allocator)
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> DynamicArray<T, P, A> {
+  const IS_ZERO_SIZED: bool = mem::size_of::<T>() == 0;
 
+  pub fn with_policy_in(size: usize, policy: P, allocator: A) -> Self {
+    let capacity = if Self::IS_ZERO_SIZED { usize::MAX } else { size };
+
+    unsafe {
       Self {
-        items,
-        layout,
+        items: Self::allocate_unsafe(&allocator, capacity),
         length: 0,
-        capacity: size,
+        capacity,
+        policy,
+        allocator,
+        marker: PhantomData,
       }
     }
   }
 
-  pub fn insert(&mut self, item: ArrayItem) {
-    if self.length < self.capacity {
-      unsafe {
-        self.insert_unsafe(self.length, item);
-      }
+  pub fn insert(&mut self, item: T) {
+    self.grow_if_full();
 
-      self.length += 1;
+    unsafe {
+      self.insert_unsafe(self.length, item);
+    }
 
-      return;
+    self.length += 1;
+  }
+
+  pub fn insert_at(&mut self, index: usize, item: T) -> Result<(), Error> {
+    if index > self.length {
+      return Err(Error::OutOfBounds { index, length: self.length });
     }
 
+    self.grow_if_full();
+
     unsafe {
-      self.copy_with_allocation_unsafe(if self.length == 0 { 1 } else { self.length * 2 });
+      let items = self.items.as_ptr();
 
-      self.insert_unsafe(self.length, item);
+      ptr::copy(items.add(index), items.add(index + 1), self.length - index);
+
+      self.insert_unsafe(index, item);
     }
 
     self.length += 1;
+
+    Ok(())
   }
 
-  pub fn index_of(&self, item: ArrayItem) -> isize {
-    for index in 0..self.length {
-      unsafe {
-        if item == *self.items.add(index) {
-          return index as isize;
+  pub fn index_of(&self, item: &T) -> Option<usize> where T: PartialEq {
+    self.iter().position(|current| current == item)
+  }
+
+  pub fn remove_at(&mut self, index: usize) -> Result<T, Error> {
+    if index >= self.length {
+      return Err(Error::OutOfBounds { index, length: self.length });
+    }
+
+    let item = unsafe { self.remove_at_unsafe(index) };
+
+    self.length -= 1;
+    self.shrink_if_sparse();
+
+    Ok(item)
+  }
+
+  pub fn pop(&mut self) -> Option<T> {
+    if self.is_empty() {
+      return None;
+    }
+
+    self.length -= 1;
+
+    let item = unsafe { ptr::read(self.items.as_ptr().add(self.length)) };
+
+    self.shrink_if_sparse();
+
+    Some(item)
+  }
+
+  pub fn swap_remove(&mut self, index: usize) -> Result<T, Error> {
+    if index >= self.length {
+      return Err(Error::OutOfBounds { index, length: self.length });
+    }
+
+    self.length -= 1;
+
+    let item = unsafe {
+      let items = self.items.as_ptr();
+      let item = ptr::read(items.add(index));
+
+      ptr::copy(items.add(self.length), items.add(index), 1);
+
+      item
+    };
+
+    self.shrink_if_sparse();
+
+    Ok(item)
+  }
+
+  pub fn truncate(&mut self, length: usize) {
+    if length >= self.length {
+      return;
+    }
+
+    let removed_count = self.length - length;
+
+    self.length = length;
+
+    unsafe {
+      ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.items.as_ptr().add(length), removed_count));
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.truncate(0);
+  }
+
+  pub fn as_slice(&self) -> &[T] {
+    unsafe { slice::from_raw_parts(self.items.as_ptr(), self.length) }
+  }
+
+  pub fn as_mut_slice(&mut self) -> &mut [T] {
+    unsafe { slice::from_raw_parts_mut(self.items.as_ptr(), self.length) }
+  }
+
+  pub fn reserve(&mut self, additional: usize) {
+    Self::handle_reserve(self.try_reserve(additional));
+  }
+
+  pub fn reserve_exact(&mut self, additional: usize) {
+    Self::handle_reserve(self.try_reserve_exact(additional));
+  }
+
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+    let required = self.required_capacity(additional)?;
+
+    if required <= self.capacity {
+      return Ok(());
+    }
+
+    let size = self.policy.grow(self.capacity, required).max(required);
+
+    unsafe { self.try_copy_with_allocation_unsafe(size) }
+  }
+
+  pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), Error> {
+    let required = self.required_capacity(additional)?;
+
+    if required <= self.capacity {
+      return Ok(());
+    }
+
+    unsafe { self.try_copy_with_allocation_unsafe(required) }
+  }
+
+  pub fn shrink_to_fit(&mut self) {
+    if Self::IS_ZERO_SIZED || self.capacity == self.length {
+      return;
+    }
+
+    unsafe {
+      self.copy_with_allocation_unsafe(self.length);
+    }
+  }
+
+  pub fn policy(&self) -> &P {
+    &self.policy
+  }
+
+  pub fn allocator(&self) -> &A {
+    &self.allocator
+  }
+
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  fn grow_if_full(&mut self) {
+    if self.length == self.capacity {
+      self.reserve(1);
+    }
+  }
+
+  fn shrink_if_sparse(&mut self) {
+    if Self::IS_ZERO_SIZED {
+      return;
+    }
+
+    if let Some(size) = self.policy.shrink(self.capacity, self.length) {
+      if size >= self.length && size < self.capacity {
+        unsafe {
+          self.copy_with_allocation_unsafe(size);
         }
       }
     }
+  }
+
+  unsafe fn insert_unsafe(&mut self, index: usize, item: T) {
+    ptr::write(self.items.as_ptr().add(index), item);
+  }
+
+  unsafe fn remove_at_unsafe(&mut self, index: usize) -> T {
+    let items = self.items.as_ptr();
+    let item = ptr::read(items.add(index));
 
-    NOT_FOUND_INDEX
+    ptr::copy(items.add(index + 1), items.add(index), self.length - index - 1);
+
+    item
+  }
+
+  unsafe fn copy_with_allocation_unsafe(&mut self, size: usize) {
+    Self::handle_reserve(self.try_copy_with_allocation_unsafe(size));
+  }
+
+  unsafe fn try_copy_with_allocation_unsafe(&mut self, size: usize) -> Result<(), Error> {
+    let initial_items = self.items;
+    let initial_capacity = self.capacity;
+
+    self.items = Self::try_allocate_unsafe(&self.allocator, size)?;
+    self.capacity = size;
+
+    ptr::copy_nonoverlapping(initial_items.as_ptr(), self.items.as_ptr(), self.length);
+
+    Self::deallocate_unsafe(&self.allocator, initial_items, initial_capacity);
+
+    Ok(())
   }
 
-  pub fn remove_at(&mut self, index: usize) {
-    assert!(index < self.length, "Array out of bounds");
+  unsafe fn allocate_unsafe(allocator: &A, size: usize) -> NonNull<T> {
+    match Self::try_allocate_unsafe(allocator, size) {
+      Ok(items) => items,
+      Err(error) => Self::handle_reserve_error(error),
+    }
+  }
 
+  unsafe fn try_allocate_unsafe(allocator: &A, size: usize) -> Result<NonNull<T>, Error> {
+    if Self::IS_ZERO_SIZED || size == 0 {
+      return Ok(NonNull::dangling());
+    }
+
+    let layout = Layout::array::<T>(size).map_err(|_| Error::CapacityOverflow)?;
+
+    if layout.size() > isize::MAX as usize {
+      return Err(Error::CapacityOverflow);
+    }
+
+    match allocator.allocate(layout) {
+      Some(items) => Ok(items.cast()),
+      None => Err(Error::AllocationFailed { layout }),
+    }
+  }
+
+  fn required_capacity(&self, additional: usize) -> Result<usize, Error> {
+    self.length.checked_add(additional).ok_or(Error::CapacityOverflow)
+  }
+
+  fn handle_reserve(result: Result<(), Error>) {
+    if let Err(error) = result {
+      Self::handle_reserve_error(error);
+    }
+  }
+
+  fn handle_reserve_error(error: Error) -> ! {
+    match error {
+      Error::AllocationFailed { layout } => handle_alloc_error(layout),
+      _ => panic!("Array capacity overflow"),
+    }
+  }
+
+  unsafe fn deallocate_unsafe(allocator: &A, items: NonNull<T>, size: usize) {
+    if Self::IS_ZERO_SIZED || size == 0 {
+      return;
+    }
+
+    allocator.deallocate(items.cast(), Layout::array::<T>(size).unwrap());
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> Drop for DynamicArray<T, P, A> {
+  fn drop(&mut self) {
     unsafe {
-      self.remove_at_unsafe(index);
+      ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.items.as_ptr(), self.length));
+
+      Self::deallocate_unsafe(&self.allocator, self.items, self.capacity);
+    }
+  }
+}
+
+impl<T, P: GrowthPolicy + Default, A: Allocator + Default> Default for DynamicArray<T, P, A> {
+  fn default() -> Self {
+    Self::with_policy_in(0, P::default(), A::default())
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> Deref for DynamicArray<T, P, A> {
+  type Target = [T];
+
+  fn deref(&self) -> &[T] {
+    self.as_slice()
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> DerefMut for DynamicArray<T, P, A> {
+  fn deref_mut(&mut self) -> &mut [T] {
+    self.as_mut_slice()
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator, I: SliceIndex<[T]>> Index<I> for DynamicArray<T, P, A> {
+  type Output = I::Output;
+
+  fn index(&self, index: I) -> &Self::Output {
+    Index::index(self.as_slice(), index)
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator, I: SliceIndex<[T]>> IndexMut<I> for DynamicArray<T, P, A> {
+  fn index_mut(&mut self, index: I) -> &mut Self::Output {
+    IndexMut::index_mut(self.as_mut_slice(), index)
+  }
+}
+
+impl<T: Clone, P: GrowthPolicy + Clone, A: Allocator + Clone> Clone for DynamicArray<T, P, A> {
+  fn clone(&self) -> Self {
+    let mut array = Self::with_policy_in(self.length, self.policy.clone(), self.allocator.clone());
+
+    array.extend(self.iter().cloned());
+
+    array
+  }
+}
+
+impl<T: PartialEq, P: GrowthPolicy, A: Allocator> PartialEq for DynamicArray<T, P, A> {
+  fn eq(&self, other: &Self) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl<T: Eq, P: GrowthPolicy, A: Allocator> Eq for DynamicArray<T, P, A> {}
+
+impl<T: Debug, P: GrowthPolicy, A: Allocator> Debug for DynamicArray<T, P, A> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> Extend<T> for DynamicArray<T, P, A> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+
+    self.reserve(iter.size_hint().0);
+
+    for item in iter {
+      self.insert(item);
     }
+  }
+}
+
+impl<T, P: GrowthPolicy + Default, A: Allocator + Default> FromIterator<T> for DynamicArray<T, P, A> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let iter = iter.into_iter();
+    let mut array = Self::with_policy_in(iter.size_hint().0, P::default(), A::default());
 
-    for i in index..self.length {
-      unsafe {
-        self.insert_unsafe(i, *self.items.add(i + 1));
+    array.extend(iter);
+
+    array
+  }
+}
+
+pub struct IntoIter<T, P: GrowthPolicy = Doubling, A: Allocator = Global> {
+  array: DynamicArray<T, P, A>,
+  front: usize,
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> IntoIterator for DynamicArray<T, P, A> {
+  type Item = T;
+  type IntoIter = IntoIter<T, P, A>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter { array: self, front: 0 }
+  }
+}
+
+impl<'a, T, P: GrowthPolicy, A: Allocator> IntoIterator for &'a DynamicArray<T, P, A> {
+  type Item = &'a T;
+  type IntoIter = slice::Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T, P: GrowthPolicy, A: Allocator> IntoIterator for &'a mut DynamicArray<T, P, A> {
+  type Item = &'a mut T;
+  type IntoIter = slice::IterMut<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> Iterator for IntoIter<T, P, A> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    if self.front == self.array.length {
+      return None;
+    }
+
+    let item = unsafe { ptr::read(self.array.items.as_ptr().add(self.front)) };
+
+    self.front += 1;
+
+    Some(item)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = self.array.length - self.front;
+
+    (remaining, Some(remaining))
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> DoubleEndedIterator for IntoIter<T, P, A> {
+  fn next_back(&mut self) -> Option<T> {
+    if self.front == self.array.length {
+      return None;
+    }
+
+    self.array.length -= 1;
+
+    Some(unsafe { ptr::read(self.array.items.as_ptr().add(self.array.length)) })
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> ExactSizeIterator for IntoIter<T, P, A> {}
+
+impl<T, P: GrowthPolicy, A: Allocator> FusedIterator for IntoIter<T, P, A> {}
+
+impl<T, P: GrowthPolicy, A: Allocator> Drop for IntoIter<T, P, A> {
+  fn drop(&mut self) {
+    let remaining = self.array.length - self.front;
+
+    unsafe {
+      ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.array.items.as_ptr().add(self.front), remaining));
+    }
+
+    self.array.length = 0;
+  }
+}
+
+impl<T: Display, P: GrowthPolicy, A: Allocator> Display for DynamicArray<T, P, A> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut formatted_items = String::new();
+
+    for index in 0..self.length {
+      let item = unsafe { (*self.items.as_ptr().add(index)).to_string() };
+
+      let delimeter = if index == self.length - 1 { "" } else { ", " };
+
+      formatted_items.push_str(&(item + delimeter));
+    }
+
+    write!(f, "[{}]", formatted_items)?;
+
+    Ok(())
+  }
+}
+
+enum SmallArrayStorage<T, const N: usize> {
+  Inline { items: [MaybeUninit<T>; N], length: usize },
+  Heap(DynamicArray<T>),
+}
+
+pub struct SmallArray<T, const N: usize> {
+  storage: SmallArrayStorage<T, N>,
+}
+
+impl<T, const N: usize> SmallArray<T, N> {
+  pub fn new() -> Self {
+    Self {
+      storage: SmallArrayStorage::Inline {
+        items: [const { MaybeUninit::uninit() }; N],
+        length: 0,
+      },
+    }
+  }
+
+  pub fn spilled(&self) -> bool {
+    matches!(self.storage, SmallArrayStorage::Heap(_))
+  }
+
+  pub fn insert(&mut self, item: T) {
+    if self.len() == N {
+      self.spill(N + 1);
+    }
+
+    match &mut self.storage {
+      SmallArrayStorage::Inline { items, length } => {
+        items[*length].write(item);
+
+        *length += 1;
       }
+      SmallArrayStorage::Heap(array) => array.insert(item),
+    }
+  }
+
+  pub fn insert_at(&mut self, index: usize, item: T) -> Result<(), Error> {
+    if index > self.len() {
+      return Err(Error::OutOfBounds { index, length: self.len() });
+    }
+
+    if self.len() == N {
+      self.spill(N + 1);
+    }
+
+    match &mut self.storage {
+      SmallArrayStorage::Inline { items, length } => unsafe {
+        let items = items.as_mut_ptr() as *mut T;
+
+        ptr::copy(items.add(index), items.add(index + 1), *length - index);
+        ptr::write(items.add(index), item);
+
+        *length += 1;
+
+        Ok(())
+      },
+      SmallArrayStorage::Heap(array) => array.insert_at(index, item),
     }
+  }
 
-    let should_shrink = self.length != 1 && self.capacity / (self.length - 1) >= ARRAY_QUARTER;
+  pub fn index_of(&self, item: &T) -> Option<usize> where T: PartialEq {
+    self.iter().position(|current| current == item)
+  }
 
-    if should_shrink {
-      unsafe {
-        self.copy_with_allocation_unsafe(self.capacity - (self.capacity / 2));
+  pub fn remove_at(&mut self, index: usize) -> Result<T, Error> {
+    if index >= self.len() {
+      return Err(Error::OutOfBounds { index, length: self.len() });
+    }
+
+    match &mut self.storage {
+      SmallArrayStorage::Inline { items, length } => {
+        let item = unsafe {
+          let items = items.as_mut_ptr() as *mut T;
+          let item = ptr::read(items.add(index));
+
+          ptr::copy(items.add(index + 1), items.add(index), *length - index - 1);
+
+          item
+        };
+
+        *length -= 1;
+
+        Ok(item)
       }
+      SmallArrayStorage::Heap(array) => array.remove_at(index),
     }
+  }
 
-    self.length -= 1;
+  pub fn pop(&mut self) -> Option<T> {
+    match &mut self.storage {
+      SmallArrayStorage::Inline { items, length } => {
+        if *length == 0 {
+          return None;
+        }
+
+        *length -= 1;
+
+        Some(unsafe { items[*length].assume_init_read() })
+      }
+      SmallArrayStorage::Heap(array) => array.pop(),
+    }
+  }
+
+  pub fn swap_remove(&mut self, index: usize) -> Result<T, Error> {
+    if index >= self.len() {
+      return Err(Error::OutOfBounds { index, length: self.len() });
+    }
+
+    let last_index = self.len() - 1;
+
+    self.as_mut_slice().swap(index, last_index);
+
+    self.pop().ok_or(Error::Empty)
   }
 
-  unsafe fn insert_unsafe(&mut self, index: usize, item: ArrayItem) {
-    *self.items.add(index) = item;
+  pub fn truncate(&mut self, length: usize) {
+    match &mut self.storage {
+      SmallArrayStorage::Inline { items, length: current_length } => {
+        if length >= *current_length {
+          return;
+        }
+
+        let removed_count = *current_length - length;
+
+        *current_length = length;
+
+        unsafe {
+          ptr::drop_in_place(ptr::slice_from_raw_parts_mut((items.as_mut_ptr() as *mut T).add(length), removed_count));
+        }
+      }
+      SmallArrayStorage::Heap(array) => array.truncate(length),
+    }
   }
 
-  unsafe fn remove_at_unsafe(&mut self, index: usize) {
-    *self.items.add(index) = ArrayItem::default();
+  pub fn clear(&mut self) {
+    self.truncate(0);
   }
 
-  unsafe fn copy_with_allocation_unsafe(&mut self, size: usize) {
-    let initial_items = self.items;
-    let initial_layout = self.layout;
+  pub fn reserve(&mut self, additional: usize) {
+    DynamicArray::<T>::handle_reserve(self.try_reserve(additional));
+  }
 
-    (self.items, self.layout) = Self::allocate_unsafe(size);
+  pub fn reserve_exact(&mut self, additional: usize) {
+    DynamicArray::<T>::handle_reserve(self.try_reserve_exact(additional));
+  }
 
-    self.capacity = size;
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+    let required = self.len().checked_add(additional).ok_or(Error::CapacityOverflow)?;
 
-    for index in 0..self.length {
-      self.insert_unsafe(index, *initial_items.add(index));
+    match &mut self.storage {
+      SmallArrayStorage::Inline { .. } if required > N => self.try_spill(required.max(N * 2)),
+      SmallArrayStorage::Inline { .. } => Ok(()),
+      SmallArrayStorage::Heap(array) => array.try_reserve(additional),
     }
+  }
 
-    dealloc(initial_items, initial_layout);
+  pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), Error> {
+    let required = self.len().checked_add(additional).ok_or(Error::CapacityOverflow)?;
+
+    match &mut self.storage {
+      SmallArrayStorage::Inline { .. } if required > N => self.try_spill(required),
+      SmallArrayStorage::Inline { .. } => Ok(()),
+      SmallArrayStorage::Heap(array) => array.try_reserve_exact(additional),
+    }
   }
 
-  unsafe fn allocate_unsafe(size: usize) -> (*mut ArrayItem, Layout) {
-    let layout = Layout::array::<ArrayItem>(size).unwrap();
+  pub fn shrink_to_fit(&mut self) {
+    let SmallArrayStorage::Heap(array) = &mut self.storage else {
+      return;
+    };
+
+    if array.len() > N {
+      array.shrink_to_fit();
+
+      return;
+    }
+
+    let mut items = [const { MaybeUninit::uninit() }; N];
+    let length = array.len();
+
+    for (index, item) in mem::take(array).into_iter().enumerate() {
+      items[index].write(item);
+    }
+
+    self.storage = SmallArrayStorage::Inline { items, length };
+  }
 
-    (alloc(layout) as *mut ArrayItem, layout)
+  pub fn as_slice(&self) -> &[T] {
+    match &self.storage {
+      SmallArrayStorage::Inline { items, length } => unsafe {
+        slice::from_raw_parts(items.as_ptr() as *const T, *length)
+      },
+      SmallArrayStorage::Heap(array) => array.as_slice(),
+    }
+  }
+
+  pub fn as_mut_slice(&mut self) -> &mut [T] {
+    match &mut self.storage {
+      SmallArrayStorage::Inline { items, length } => unsafe {
+        slice::from_raw_parts_mut(items.as_mut_ptr() as *mut T, *length)
+      },
+      SmallArrayStorage::Heap(array) => array.as_mut_slice(),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    match &self.storage {
+      SmallArrayStorage::Inline { length, .. } => *length,
+      SmallArrayStorage::Heap(array) => array.len(),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn capacity(&self) -> usize {
+    match &self.storage {
+      SmallArrayStorage::Inline { .. } => N,
+      SmallArrayStorage::Heap(array) => array.capacity(),
+    }
+  }
+
+  fn spill(&mut self, required: usize) {
+    DynamicArray::<T>::handle_reserve(self.try_spill(required.max(N * 2)));
+  }
+
+  fn try_spill(&mut self, capacity: usize) -> Result<(), Error> {
+    let SmallArrayStorage::Inline { items, length } = &mut self.storage else {
+      return Ok(());
+    };
+
+    let mut array = DynamicArray::new(0);
+    array.try_reserve_exact(capacity)?;
+
+    for item in &items[..*length] {
+      array.insert(unsafe { item.assume_init_read() });
+    }
+
+    *length = 0;
+
+    self.storage = SmallArrayStorage::Heap(array);
+
+    Ok(())
   }
 }
 
-impl Display for DynamicArray {
+impl<T, const N: usize> Drop for SmallArray<T, N> {
+  fn drop(&mut self) {
+    if let SmallArrayStorage::Inline { .. } = self.storage {
+      self.clear();
+    }
+  }
+}
+
+impl<T, const N: usize> Default for SmallArray<T, N> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, const N: usize> Deref for SmallArray<T, N> {
+  type Target = [T];
+
+  fn deref(&self) -> &[T] {
+    self.as_slice()
+  }
+}
+
+impl<T, const N: usize> DerefMut for SmallArray<T, N> {
+  fn deref_mut(&mut self) -> &mut [T] {
+    self.as_mut_slice()
+  }
+}
+
+impl<T, const N: usize, I: SliceIndex<[T]>> Index<I> for SmallArray<T, N> {
+  type Output = I::Output;
+
+  fn index(&self, index: I) -> &Self::Output {
+    Index::index(self.as_slice(), index)
+  }
+}
+
+impl<T, const N: usize, I: SliceIndex<[T]>> IndexMut<I> for SmallArray<T, N> {
+  fn index_mut(&mut self, index: I) -> &mut Self::Output {
+    IndexMut::index_mut(self.as_mut_slice(), index)
+  }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallArray<T, N> {
+  fn clone(&self) -> Self {
+    self.iter().cloned().collect()
+  }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for SmallArray<T, N> {
+  fn eq(&self, other: &Self) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl<T: Eq, const N: usize> Eq for SmallArray<T, N> {}
+
+impl<T: Debug, const N: usize> Debug for SmallArray<T, N> {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    unsafe {
-      let mut formatted_items = String::new();
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T, const N: usize> Extend<T> for SmallArray<T, N> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+
+    self.reserve(iter.size_hint().0);
+
+    for item in iter {
+      self.insert(item);
+    }
+  }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallArray<T, N> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut array = Self::new();
 
-      for index in 0..self.length {
-        let item = (*self.items.add(index)).to_string();
+    array.extend(iter);
 
-        let delimeter = if index == self.length - 1 { "" } else { ", " };
+    array
+  }
+}
+
+enum SmallIntoIterInner<T, const N: usize> {
+  Inline { items: [MaybeUninit<T>; N], front: usize, back: usize },
+  Heap(IntoIter<T>),
+}
 
-        formatted_items.push_str(&(item + delimeter));
+pub struct SmallIntoIter<T, const N: usize> {
+  inner: SmallIntoIterInner<T, N>,
+}
+
+impl<T, const N: usize> IntoIterator for SmallArray<T, N> {
+  type Item = T;
+  type IntoIter = SmallIntoIter<T, N>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    let array = ManuallyDrop::new(self);
+
+    let inner = match unsafe { ptr::read(&array.storage) } {
+      SmallArrayStorage::Inline { items, length } => SmallIntoIterInner::Inline { items, front: 0, back: length },
+      SmallArrayStorage::Heap(array) => SmallIntoIterInner::Heap(array.into_iter()),
+    };
+
+    SmallIntoIter { inner }
+  }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallArray<T, N> {
+  type Item = &'a T;
+  type IntoIter = slice::Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallArray<T, N> {
+  type Item = &'a mut T;
+  type IntoIter = slice::IterMut<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+impl<T, const N: usize> Iterator for SmallIntoIter<T, N> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    match &mut self.inner {
+      SmallIntoIterInner::Inline { items, front, back } => {
+        if front == back {
+          return None;
+        }
+
+        *front += 1;
+
+        Some(unsafe { items[*front - 1].assume_init_read() })
       }
+      SmallIntoIterInner::Heap(iter) => iter.next(),
+    }
+  }
 
-      write!(f, "[{}]", formatted_items)?;
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    match &self.inner {
+      SmallIntoIterInner::Inline { front, back, .. } => (back - front, Some(back - front)),
+      SmallIntoIterInner::Heap(iter) => iter.size_hint(),
     }
+  }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for SmallIntoIter<T, N> {
+  fn next_back(&mut self) -> Option<T> {
+    match &mut self.inner {
+      SmallIntoIterInner::Inline { items, front, back } => {
+        if front == back {
+          return None;
+        }
+
+        *back -= 1;
+
+        Some(unsafe { items[*back].assume_init_read() })
+      }
+      SmallIntoIterInner::Heap(iter) => iter.next_back(),
+    }
+  }
+}
+
+impl<T, const N: usize> ExactSizeIterator for SmallIntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for SmallIntoIter<T, N> {}
+
+impl<T, const N: usize> Drop for SmallIntoIter<T, N> {
+  fn drop(&mut self) {
+    if let SmallIntoIterInner::Inline { items, front, back } = &mut self.inner {
+      for item in &mut items[*front..*back] {
+        unsafe {
+          item.assume_init_drop();
+        }
+      }
+    }
+  }
+}
+
+impl<T: Display, const N: usize> Display for SmallArray<T, N> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let formatted_items = self.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(", ");
+
+    write!(f, "[{}]", formatted_items)?;
 
     Ok(())
   }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use std::alloc::Layout;
+  use std::cell::RefCell;
+  use std::collections::HashMap;
+  use std::ptr::NonNull;
+
+  use super::*;
+  use crate::allocators::BumpArena;
+
+  #[derive(Default)]
+  struct CountingAllocator {
+    live: RefCell<HashMap<usize, Layout>>,
+    allocations: RefCell<usize>,
+    frees: RefCell<usize>,
+  }
+
+  unsafe impl Allocator for CountingAllocator {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+      let pointer = Global.allocate(layout)?;
+
+      assert!(self.live.borrow_mut().insert(pointer.as_ptr() as usize, layout).is_none());
+      *self.allocations.borrow_mut() += 1;
+
+      Some(pointer)
+    }
+
+    unsafe fn deallocate(&self, pointer: NonNull<u8>, layout: Layout) {
+      let allocated = self.live.borrow_mut().remove(&(pointer.as_ptr() as usize)).expect("freed unknown pointer");
+
+      assert_eq!(allocated, layout);
+      *self.frees.borrow_mut() += 1;
+
+      unsafe { Global.deallocate(pointer, layout) }
+    }
+  }
+
+  #[test]
+  fn new_in_pairs_every_allocation_with_a_matching_free() {
+    let allocator = CountingAllocator::default();
+
+    {
+      let mut array = DynamicArray::new_in(0, &allocator);
+
+      for round in 0..5 {
+        for index in 0..100 * (round + 1) {
+          array.insert(index.to_string());
+        }
+
+        while array.len() > 3 {
+          array.pop();
+        }
+
+        array.shrink_to_fit();
+        assert_eq!(array.capacity(), 3);
+
+        array.reserve(50);
+        array.remove_at(0).unwrap();
+        array.truncate(1);
+      }
+
+      let mut exact = DynamicArray::new_in(4, &allocator);
+      exact.try_reserve_exact(10).unwrap();
+      exact.insert(1u64);
+      exact.shrink_to_fit();
+      drop(exact.into_iter());
+    }
+
+    assert!(*allocator.allocations.borrow() > 10);
+    assert!(allocator.live.borrow().is_empty());
+    assert_eq!(*allocator.allocations.borrow(), *allocator.frees.borrow());
+  }
+
+  #[test]
+  fn new_in_allocates_from_a_bump_arena() {
+    let arena = BumpArena::new();
+
+    {
+      let mut array = DynamicArray::with_policy_in(0, Factor(1.5), &arena);
+
+      for value in 0..10000u64 {
+        array.insert(value);
+      }
+
+      assert_eq!(array[9999], 9999);
+      array.shrink_to_fit();
+      assert_eq!(array.len(), 10000);
+    }
+
+    assert!(arena.allocated_bytes() >= 10000 * mem::size_of::<u64>());
+  }
+
+  #[test]
+  fn small_into_iter_drops_unconsumed_items() {
+    let counter = std::rc::Rc::new(());
+
+    let mut inline = SmallArray::<_, 4>::new();
+    inline.extend((0..3).map(|_| counter.clone()));
+
+    let mut iter = inline.into_iter();
+    assert!(iter.next().is_some());
+    assert!(iter.next_back().is_some());
+    assert_eq!(iter.len(), 1);
+    drop(iter);
+
+    let mut spilled = SmallArray::<_, 2>::new();
+    spilled.extend((0..5).map(|_| counter.clone()));
+
+    let mut iter = spilled.into_iter();
+    assert!(iter.next().is_some());
+    assert_eq!(iter.len(), 4);
+    drop(iter);
+
+    assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+  }
+
+  #[test]
+  fn small_array_reserve_spills_to_requested_capacity() {
+    let mut array = SmallArray::<u32, 4>::new();
+    array.extend([1, 2]);
+
+    array.try_reserve(2).unwrap();
+    assert!(!array.spilled());
+
+    array.try_reserve_exact(5).unwrap();
+    assert!(array.spilled());
+    assert_eq!(array.capacity(), 7);
+    assert_eq!(array.as_slice(), &[1, 2]);
+
+    array.reserve_exact(10);
+    assert!(array.capacity() >= 12);
+
+    let mut inline = SmallArray::<u32, 4>::new();
+    inline.reserve(5);
+    assert_eq!(inline.capacity(), 8);
+
+    assert_eq!(array.try_reserve(usize::MAX), Err(Error::CapacityOverflow));
+    assert_eq!(inline.try_reserve_exact(usize::MAX), Err(Error::CapacityOverflow));
+  }
+}
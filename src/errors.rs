@@ -0,0 +1,33 @@
+use std::alloc::Layout;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+  OutOfBounds { index: usize, length: usize },
+  Empty,
+  CapacityExceeded { capacity: usize },
+  CapacityOverflow,
+  AllocationFailed { layout: Layout },
+  NodeMissing,
+  InvalidHandle,
+  Closed,
+  Timeout,
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Error::OutOfBounds { index, length } => write!(f, "Index {} is out of bounds for length {}", index, length),
+      Error::Empty => write!(f, "Container is empty"),
+      Error::CapacityExceeded { capacity } => write!(f, "Capacity of {} is exceeded", capacity),
+      Error::CapacityOverflow => write!(f, "Capacity overflow"),
+      Error::AllocationFailed { layout } => write!(f, "Failed to allocate {} bytes", layout.size()),
+      Error::NodeMissing => write!(f, "Node is not exist"),
+      Error::InvalidHandle => write!(f, "Handle is invalid or stale"),
+      Error::Closed => write!(f, "Container is closed"),
+      Error::Timeout => write!(f, "Operation timed out"),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
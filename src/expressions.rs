@@ -0,0 +1,433 @@
+use std::fmt::{Display, Formatter};
+
+use crate::stacks::Stack;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+  Add,
+  Subtract,
+  Multiply,
+  Divide,
+  Remainder,
+  Power,
+  Negate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Associativity {
+  Left,
+  Right,
+}
+
+impl Operator {
+  pub fn from_symbol(symbol: char) -> Option<Self> {
+    match symbol {
+      '+' => Some(Operator::Add),
+      '-' => Some(Operator::Subtract),
+      '*' => Some(Operator::Multiply),
+      '/' => Some(Operator::Divide),
+      '%' => Some(Operator::Remainder),
+      '^' => Some(Operator::Power),
+      '~' => Some(Operator::Negate),
+      _ => None,
+    }
+  }
+
+  pub fn symbol(self) -> char {
+    match self {
+      Operator::Add => '+',
+      Operator::Subtract => '-',
+      Operator::Multiply => '*',
+      Operator::Divide => '/',
+      Operator::Remainder => '%',
+      Operator::Power => '^',
+      Operator::Negate => '~',
+    }
+  }
+
+  pub fn precedence(self) -> u8 {
+    match self {
+      Operator::Add | Operator::Subtract => 1,
+      Operator::Multiply | Operator::Divide | Operator::Remainder => 2,
+      Operator::Negate => 3,
+      Operator::Power => 4,
+    }
+  }
+
+  pub fn associativity(self) -> Associativity {
+    match self {
+      Operator::Power | Operator::Negate => Associativity::Right,
+      _ => Associativity::Left,
+    }
+  }
+
+  pub fn is_unary(self) -> bool {
+    self == Operator::Negate
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bracket {
+  Round,
+  Square,
+  Curly,
+}
+
+impl Bracket {
+  fn from_symbol(symbol: char) -> Option<(Self, bool)> {
+    match symbol {
+      '(' => Some((Bracket::Round, true)),
+      ')' => Some((Bracket::Round, false)),
+      '[' => Some((Bracket::Square, true)),
+      ']' => Some((Bracket::Square, false)),
+      '{' => Some((Bracket::Curly, true)),
+      '}' => Some((Bracket::Curly, false)),
+      _ => None,
+    }
+  }
+
+  fn symbol(self, is_opening: bool) -> char {
+    match (self, is_opening) {
+      (Bracket::Round, true) => '(',
+      (Bracket::Round, false) => ')',
+      (Bracket::Square, true) => '[',
+      (Bracket::Square, false) => ']',
+      (Bracket::Curly, true) => '{',
+      (Bracket::Curly, false) => '}',
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenKind {
+  Number(String),
+  Operator(Operator),
+  Open(Bracket),
+  Close(Bracket),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+  pub kind: TokenKind,
+  pub column: usize,
+}
+
+impl Display for Token {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match &self.kind {
+      TokenKind::Number(number) => write!(f, "{}", number),
+      TokenKind::Operator(operator) => write!(f, "{}", operator.symbol()),
+      TokenKind::Open(bracket) => write!(f, "{}", bracket.symbol(true)),
+      TokenKind::Close(bracket) => write!(f, "{}", bracket.symbol(false)),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpressionErrorKind {
+  UnexpectedCharacter(char),
+  UnexpectedToken,
+  UnmatchedOpening,
+  UnmatchedClosing,
+  MismatchedBracket,
+  MissingOperand,
+  MissingOperator,
+  InvalidNumber,
+  DivisionByZero,
+  InvalidExponent,
+  Overflow,
+  EmptyExpression,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExpressionError {
+  pub kind: ExpressionErrorKind,
+  pub column: usize,
+}
+
+impl ExpressionError {
+  fn new(kind: ExpressionErrorKind, column: usize) -> Self {
+    Self { kind, column }
+  }
+}
+
+impl Display for ExpressionError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let message = match self.kind {
+      ExpressionErrorKind::UnexpectedCharacter(character) => format!("Unexpected character '{}'", character),
+      ExpressionErrorKind::UnexpectedToken => "Unexpected token".to_string(),
+      ExpressionErrorKind::UnmatchedOpening => "Opening bracket is never closed".to_string(),
+      ExpressionErrorKind::UnmatchedClosing => "Closing bracket has no opening bracket".to_string(),
+      ExpressionErrorKind::MismatchedBracket => "Closing bracket does not match the opening bracket".to_string(),
+      ExpressionErrorKind::MissingOperand => "Operand is missing".to_string(),
+      ExpressionErrorKind::MissingOperator => "Operator is missing".to_string(),
+      ExpressionErrorKind::InvalidNumber => "Number is invalid".to_string(),
+      ExpressionErrorKind::DivisionByZero => "Division by zero".to_string(),
+      ExpressionErrorKind::InvalidExponent => "Exponent is invalid".to_string(),
+      ExpressionErrorKind::Overflow => "Arithmetic overflow".to_string(),
+      ExpressionErrorKind::EmptyExpression => "Expression is empty".to_string(),
+    };
+
+    write!(f, "{} at column {}", message, self.column)
+  }
+}
+
+impl std::error::Error for ExpressionError {}
+
+pub trait Operand: Copy {
+  fn parse(text: &str) -> Option<Self>;
+  fn negate(self) -> Result<Self, ExpressionErrorKind>;
+  fn apply(operator: Operator, left: Self, right: Self) -> Result<Self, ExpressionErrorKind>;
+}
+
+impl Operand for i64 {
+  fn parse(text: &str) -> Option<Self> {
+    text.parse().ok()
+  }
+
+  fn negate(self) -> Result<Self, ExpressionErrorKind> {
+    self.checked_neg().ok_or(ExpressionErrorKind::Overflow)
+  }
+
+  fn apply(operator: Operator, left: Self, right: Self) -> Result<Self, ExpressionErrorKind> {
+    if right == 0 && matches!(operator, Operator::Divide | Operator::Remainder) {
+      return Err(ExpressionErrorKind::DivisionByZero);
+    }
+
+    let result = match operator {
+      Operator::Add => left.checked_add(right),
+      Operator::Subtract => left.checked_sub(right),
+      Operator::Multiply => left.checked_mul(right),
+      Operator::Divide => left.checked_div(right),
+      Operator::Remainder => left.checked_rem(right),
+      Operator::Power => {
+        let exponent = u32::try_from(right).map_err(|_| ExpressionErrorKind::InvalidExponent)?;
+
+        left.checked_pow(exponent)
+      }
+      Operator::Negate => return Err(ExpressionErrorKind::UnexpectedToken),
+    };
+
+    result.ok_or(ExpressionErrorKind::Overflow)
+  }
+}
+
+impl Operand for f64 {
+  fn parse(text: &str) -> Option<Self> {
+    text.parse().ok()
+  }
+
+  fn negate(self) -> Result<Self, ExpressionErrorKind> {
+    Ok(-self)
+  }
+
+  fn apply(operator: Operator, left: Self, right: Self) -> Result<Self, ExpressionErrorKind> {
+    if right == 0.0 && matches!(operator, Operator::Divide | Operator::Remainder) {
+      return Err(ExpressionErrorKind::DivisionByZero);
+    }
+
+    let result = match operator {
+      Operator::Add => left + right,
+      Operator::Subtract => left - right,
+      Operator::Multiply => left * right,
+      Operator::Divide => left / right,
+      Operator::Remainder => left % right,
+      Operator::Power => left.powf(right),
+      Operator::Negate => return Err(ExpressionErrorKind::UnexpectedToken),
+    };
+
+    if result.is_finite() {
+      Ok(result)
+    } else {
+      Err(ExpressionErrorKind::Overflow)
+    }
+  }
+}
+
+pub fn check_balanced(input: &str) -> Result<(), ExpressionError> {
+  let mut openings = Stack::new();
+
+  for (index, symbol) in input.chars().enumerate() {
+    let column = index + 1;
+
+    match Bracket::from_symbol(symbol) {
+      Some((bracket, true)) => openings.push((bracket, column)).unwrap(),
+      Some((bracket, false)) => match openings.pop() {
+        Ok((opening, _)) if opening == bracket => {}
+        Ok(_) => return Err(ExpressionError::new(ExpressionErrorKind::MismatchedBracket, column)),
+        Err(_) => return Err(ExpressionError::new(ExpressionErrorKind::UnmatchedClosing, column)),
+      },
+      None => {}
+    }
+  }
+
+  match openings.pop() {
+    Ok((_, column)) => Err(ExpressionError::new(ExpressionErrorKind::UnmatchedOpening, column)),
+    Err(_) => Ok(()),
+  }
+}
+
+pub fn is_balanced(input: &str) -> bool {
+  check_balanced(input).is_ok()
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<Token>, ExpressionError> {
+  let mut tokens = Vec::new();
+  let mut characters = input.chars().enumerate().peekable();
+
+  while let Some((index, symbol)) = characters.next() {
+    let column = index + 1;
+
+    let kind = if symbol.is_whitespace() {
+      continue;
+    } else if symbol.is_ascii_digit() || symbol == '.' {
+      let mut number = symbol.to_string();
+
+      while let Some((_, next)) = characters.next_if(|(_, next)| next.is_ascii_digit() || *next == '.') {
+        number.push(next);
+      }
+
+      TokenKind::Number(number)
+    } else if let Some(operator) = Operator::from_symbol(symbol) {
+      TokenKind::Operator(operator)
+    } else if let Some((bracket, is_opening)) = Bracket::from_symbol(symbol) {
+      if is_opening { TokenKind::Open(bracket) } else { TokenKind::Close(bracket) }
+    } else {
+      return Err(ExpressionError::new(ExpressionErrorKind::UnexpectedCharacter(symbol), column));
+    };
+
+    tokens.push(Token { kind, column });
+  }
+
+  Ok(tokens)
+}
+
+pub fn to_postfix(input: &str) -> Result<Vec<Token>, ExpressionError> {
+  let tokens = tokenize(input)?;
+  let end_column = input.chars().count() + 1;
+
+  if tokens.is_empty() {
+    return Err(ExpressionError::new(ExpressionErrorKind::EmptyExpression, end_column));
+  }
+
+  let mut output = Vec::with_capacity(tokens.len());
+  let mut operators: Stack<Token> = Stack::new();
+  let mut expects_operand = true;
+
+  for mut token in tokens {
+    match token.kind {
+      TokenKind::Number(_) | TokenKind::Open(_) if !expects_operand => {
+        return Err(ExpressionError::new(ExpressionErrorKind::MissingOperator, token.column));
+      }
+      TokenKind::Number(_) => {
+        output.push(token);
+
+        expects_operand = false;
+      }
+      TokenKind::Open(_) => operators.push(token).unwrap(),
+      TokenKind::Close(_) | TokenKind::Operator(_) if expects_operand && !is_prefix(&token) => {
+        return Err(ExpressionError::new(ExpressionErrorKind::MissingOperand, token.column));
+      }
+      TokenKind::Close(bracket) => {
+        loop {
+          let top = operators.pop().map_err(|_| ExpressionError::new(ExpressionErrorKind::UnmatchedClosing, token.column))?;
+
+          match top.kind {
+            TokenKind::Open(opening) if opening == bracket => break,
+            TokenKind::Open(_) => return Err(ExpressionError::new(ExpressionErrorKind::MismatchedBracket, token.column)),
+            _ => output.push(top),
+          }
+        }
+      }
+      TokenKind::Operator(Operator::Negate) if !expects_operand => {
+        return Err(ExpressionError::new(ExpressionErrorKind::MissingOperator, token.column));
+      }
+      TokenKind::Operator(_) if expects_operand => {
+        token.kind = TokenKind::Operator(Operator::Negate);
+
+        operators.push(token).unwrap();
+      }
+      TokenKind::Operator(operator) => {
+        while let Ok(top) = operators.peek() {
+          let TokenKind::Operator(top_operator) = top.kind else {
+            break;
+          };
+
+          let is_popped = top_operator.precedence() > operator.precedence()
+            || (top_operator.precedence() == operator.precedence() && operator.associativity() == Associativity::Left);
+
+          if !is_popped {
+            break;
+          }
+
+          output.push(operators.pop().unwrap());
+        }
+
+        operators.push(token).unwrap();
+
+        expects_operand = true;
+      }
+    }
+  }
+
+  if expects_operand {
+    return Err(ExpressionError::new(ExpressionErrorKind::MissingOperand, end_column));
+  }
+
+  while let Ok(top) = operators.pop() {
+    if let TokenKind::Open(_) = top.kind {
+      return Err(ExpressionError::new(ExpressionErrorKind::UnmatchedOpening, top.column));
+    }
+
+    output.push(top);
+  }
+
+  Ok(output)
+}
+
+fn is_prefix(token: &Token) -> bool {
+  matches!(token.kind, TokenKind::Operator(Operator::Subtract | Operator::Negate))
+}
+
+pub fn evaluate_postfix<N: Operand>(tokens: &[Token]) -> Result<N, ExpressionError> {
+  let mut operands: Stack<N> = Stack::new();
+
+  for token in tokens {
+    let missing_operand = || ExpressionError::new(ExpressionErrorKind::MissingOperand, token.column);
+
+    let value = match &token.kind {
+      TokenKind::Number(number) => {
+        N::parse(number).ok_or(ExpressionError::new(ExpressionErrorKind::InvalidNumber, token.column))?
+      }
+      TokenKind::Operator(Operator::Negate) => {
+        let operand = operands.pop().map_err(|_| missing_operand())?;
+
+        operand.negate().map_err(|kind| ExpressionError::new(kind, token.column))?
+      }
+      TokenKind::Operator(operator) => {
+        let right = operands.pop().map_err(|_| missing_operand())?;
+        let left = operands.pop().map_err(|_| missing_operand())?;
+
+        N::apply(*operator, left, right).map_err(|kind| ExpressionError::new(kind, token.column))?
+      }
+      TokenKind::Open(_) | TokenKind::Close(_) => {
+        return Err(ExpressionError::new(ExpressionErrorKind::UnexpectedToken, token.column));
+      }
+    };
+
+    operands.push(value).unwrap();
+  }
+
+  let end_column = tokens.last().map_or(1, |token| token.column + 1);
+  let result = operands.pop().map_err(|_| ExpressionError::new(ExpressionErrorKind::EmptyExpression, end_column))?;
+
+  if !operands.is_empty() {
+    return Err(ExpressionError::new(ExpressionErrorKind::MissingOperator, end_column));
+  }
+
+  Ok(result)
+}
+
+pub fn evaluate<N: Operand>(input: &str) -> Result<N, ExpressionError> {
+  evaluate_postfix(&to_postfix(input)?)
+}
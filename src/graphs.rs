@@ -1,5 +1,7 @@
 use std::{collections::{HashMap, HashSet}, hash::Hash, fmt::Display};
 
+use crate::errors::Error;
+
 struct Node<'a, T> {
   value: &'a T,
   relatives: HashSet<&'a T>,
@@ -67,9 +69,9 @@ impl<'a, T: Eq + Hash + Clone> Graph<'a, T> {
     self.nodes.insert(value, Node::new(value));
   }
 
-  pub fn remove_node(&mut self, value: &'a T) -> Result<(), &'static str> {
+  pub fn remove_node(&mut self, value: &'a T) -> Result<(), Error> {
     if !self.is_node_exists(value) {
-      return Err("Node is not exist");
+      return Err(Error::NodeMissing);
     }
 
     for node in self.nodes.values_mut() {
@@ -81,13 +83,13 @@ impl<'a, T: Eq + Hash + Clone> Graph<'a, T> {
     Ok(())
   }
 
-  pub fn add_edge(&mut self, from: &T, to: &'a T) -> Result<(), &'static str> {
+  pub fn add_edge(&mut self, from: &T, to: &'a T) -> Result<(), Error> {
     if !self.is_node_exists(from) {
-      return Err("From node is not exist");
+      return Err(Error::NodeMissing);
     }
 
     if !self.is_node_exists(to) {
-      return Err("To node is not exist");
+      return Err(Error::NodeMissing);
     }
 
     let node = self.nodes.get_mut(from).unwrap();
@@ -1,105 +1,219 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::slice;
+
+use crate::errors::Error;
+
 #[derive(PartialEq)]
 enum HeapChild {
   Left,
   Right,
 }
 
-pub struct Heap<T> {
+pub trait Comparator<T> {
+  fn compare(&self, left: &T, right: &T) -> Ordering;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MaxFirst;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinFirst;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ByKey<F>(pub F);
+
+impl<T: Ord> Comparator<T> for MaxFirst {
+  fn compare(&self, left: &T, right: &T) -> Ordering {
+    left.cmp(right)
+  }
+}
+
+impl<T: Ord> Comparator<T> for MinFirst {
+  fn compare(&self, left: &T, right: &T) -> Ordering {
+    right.cmp(left)
+  }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> Comparator<T> for ByKey<F> {
+  fn compare(&self, left: &T, right: &T) -> Ordering {
+    (self.0)(left).cmp(&(self.0)(right))
+  }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Comparator<T> for F {
+  fn compare(&self, left: &T, right: &T) -> Ordering {
+    self(left, right)
+  }
+}
+
+pub struct Heap<T, C: Comparator<T> = MaxFirst> {
   items: Vec<T>,
+  comparator: C,
 }
 
-impl Heap<i32> {
+impl<T: Ord> Heap<T> {
   pub fn new() -> Self {
-    Self { items: Vec::new() }
+    Self::with_comparator(MaxFirst)
+  }
+
+  pub fn heapify(items: Vec<T>) -> Self {
+    Self::heapify_with(items, MaxFirst)
+  }
+}
+
+impl<T, C: Comparator<T>> Heap<T, C> {
+  pub fn with_comparator(comparator: C) -> Self {
+    Self { items: Vec::new(), comparator }
   }
 
-  pub fn heapify(items: Vec<i32>) -> Self {
+  pub fn heapify_with(items: Vec<T>, comparator: C) -> Self {
     let length = items.len();
 
-    let mut heap = Self { items };
+    let mut heap = Self { items, comparator };
 
-    for index in 1..length {
-      heap.bubble(index);
+    for index in (0..length / 2).rev() {
+      heap.dive(index, &mut |_, _| {});
     }
 
     heap
   }
 
-  pub fn insert(&mut self, value: i32) {
-    self.items.push(value);
-
-    self.bubble(self.items.len() - 1);
+  pub fn insert(&mut self, value: T) {
+    self.insert_tracked(value, &mut |_, _| {});
   }
 
-  pub fn remove(&mut self) -> Result<i32, &'static str> {
+  pub fn remove(&mut self) -> Result<T, Error> {
     if self.is_empty() {
-      return Err("Heap is empty");
+      return Err(Error::Empty);
     }
 
-    let last_child = self.items.pop().unwrap();
+    Ok(self.remove_at_tracked(0, &mut |_, _| {}))
+  }
 
-    if self.is_empty() {
-      return Ok(last_child);
+  pub fn peek(&self) -> Result<&T, Error> {
+    self.items.first().ok_or(Error::Empty)
+  }
+
+  pub fn len(&self) -> usize {
+    self.items.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.items.len() == 0
+  }
+
+  pub fn clear(&mut self) {
+    self.items.clear();
+  }
+
+  pub fn comparator(&self) -> &C {
+    &self.comparator
+  }
+
+  pub fn iter(&self) -> slice::Iter<'_, T> {
+    self.items.iter()
+  }
+
+  pub fn into_sorted_vec(mut self) -> Vec<T> {
+    let mut sorted = Vec::with_capacity(self.items.len());
+
+    while let Ok(value) = self.remove() {
+      sorted.push(value);
     }
 
-    let first = self.items[0];
+    sorted
+  }
 
-    self.items[0] = last_child;
-    self.dive(0);
+  pub(crate) fn get(&self, index: usize) -> Option<&T> {
+    self.items.get(index)
+  }
 
-    Ok(first)
+  pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+    self.items.get_mut(index)
   }
 
-  pub fn is_empty(&self) -> bool {
-    self.items.len() == 0
+  pub(crate) fn insert_tracked<F>(&mut self, value: T, on_move: &mut F) where F: FnMut(&T, usize) {
+    let index = self.items.len();
+
+    self.items.push(value);
+    on_move(&self.items[index], index);
+
+    self.bubble(index, on_move);
+  }
+
+  pub(crate) fn remove_at_tracked<F>(&mut self, index: usize, on_move: &mut F) -> T where F: FnMut(&T, usize) {
+    let removed = self.items.swap_remove(index);
+
+    if index < self.items.len() {
+      on_move(&self.items[index], index);
+
+      self.update_at_tracked(index, on_move);
+    }
+
+    removed
+  }
+
+  pub(crate) fn update_at_tracked<F>(&mut self, index: usize, on_move: &mut F) where F: FnMut(&T, usize) {
+    if index > 0 && self.is_higher(index, Self::get_parent_index(index)) {
+      self.bubble(index, on_move);
+    } else {
+      self.dive(index, on_move);
+    }
   }
 
-  fn bubble(&mut self, index: usize) {
+  fn swap<F>(&mut self, index: usize, other_index: usize, on_move: &mut F) where F: FnMut(&T, usize) {
+    self.items.swap(index, other_index);
+
+    on_move(&self.items[index], index);
+    on_move(&self.items[other_index], other_index);
+  }
+
+  fn is_higher(&self, index: usize, other_index: usize) -> bool {
+    self.comparator.compare(&self.items[index], &self.items[other_index]) == Ordering::Greater
+  }
+
+  fn bubble<F>(&mut self, index: usize, on_move: &mut F) where F: FnMut(&T, usize) {
     if index == 0 {
       return;
     }
 
     let parent_index = Self::get_parent_index(index);
 
-    if self.items[parent_index] >= self.items[index] {
+    if !self.is_higher(index, parent_index) {
       return;
     }
 
-    self.items.swap(parent_index, index);
+    self.swap(parent_index, index, on_move);
 
-    self.bubble(parent_index);
+    self.bubble(parent_index, on_move);
   }
 
-  fn dive(&mut self, index: usize) {
+  fn dive<F>(&mut self, index: usize, on_move: &mut F) where F: FnMut(&T, usize) {
     if index >= self.items.len() {
       return;
     }
 
-    let swap_index = self.get_dive_swap_index(index);
-
-    if swap_index.is_none() {
+    let Some(swap_index) = self.get_dive_swap_index(index) else {
       return;
-    }
+    };
 
-    let swap_index = swap_index.unwrap();
+    self.swap(swap_index, index, on_move);
 
-    self.items.swap(swap_index, index);
-
-    self.dive(swap_index);
+    self.dive(swap_index, on_move);
   }
 
   fn get_dive_swap_index(&self, index: usize) -> Option<usize> {
-    let (left_child, left_child_index) = self.left_child(index);
-    let (right_child, right_child_index) = self.right_child(index);
-    let value = self.items[index];
+    let left_child_index = Self::get_child_index(index, HeapChild::Left);
+    let right_child_index = Self::get_child_index(index, HeapChild::Right);
 
     let mut swap_index = index;
 
-    if left_child.is_some() && *left_child.unwrap() > value {
+    if left_child_index < self.items.len() && self.is_higher(left_child_index, swap_index) {
       swap_index = left_child_index;
     }
 
-    if right_child.is_some() && *right_child.unwrap() > value {
+    if right_child_index < self.items.len() && self.is_higher(right_child_index, swap_index) {
       swap_index = right_child_index;
     }
 
@@ -110,23 +224,37 @@ impl Heap<i32> {
     Some(swap_index)
   }
 
-  fn left_child(&self, index: usize) -> (Option<&i32>, usize) {
-    let left_child_index = Self::get_child_index(index, HeapChild::Left);
+  fn get_child_index(parent_index: usize, child_type: HeapChild) -> usize {
+    parent_index * 2 + if child_type == HeapChild::Left { 1 } else { 2 }
+  }
 
-    (self.items.get(left_child_index), left_child_index)
+  fn get_parent_index(child_index: usize) -> usize {
+    (child_index - 1) / 2
   }
+}
 
-  fn right_child(&self, index: usize) -> (Option<&i32>, usize) {
-    let right_child_index = Self::get_child_index(index, HeapChild::Right);
+impl<T: Ord> Default for Heap<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
 
-    (self.items.get(right_child_index), right_child_index)
+impl<T: Debug, C: Comparator<T>> Debug for Heap<T, C> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.items.iter()).finish()
   }
+}
 
-  fn get_child_index(parent_index: usize, child_type: HeapChild) -> usize {
-    parent_index * 2 + if child_type == HeapChild::Left { 1 } else { 2 }
+impl<T, C: Comparator<T>> Extend<T> for Heap<T, C> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for value in iter {
+      self.insert(value);
+    }
   }
+}
 
-  fn get_parent_index(child_index: usize) -> usize {
-    (child_index - 1) / 2
+impl<T: Ord> FromIterator<T> for Heap<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self::heapify(iter.into_iter().collect())
   }
-}
\ No newline at end of file
+}
@@ -1,9 +1,15 @@
+pub mod allocators;
+
 pub mod arrays;
 
+pub mod errors;
+
 pub mod lists;
 
 pub mod stacks;
 
+pub mod expressions;
+
 pub mod queues;
 
 pub mod hash_tables;
@@ -1,43 +1,64 @@
-use std::{fmt::Display, rc::Rc, cell::RefCell};
+use std::{fmt::{Debug, Display}, rc::{Rc, Weak}, cell::RefCell, cmp::Ordering, iter::FusedIterator, marker::PhantomData};
+use std::collections::{HashSet, hash_map::RandomState};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::{Bound, RangeBounds, RangeFull};
+use std::sync::{self, Arc, Mutex, MutexGuard, PoisonError};
 
-const NOT_FOUND_INDEX: isize = -1;
+use crate::errors::Error;
 
-type OptionalNode<T> = Option<Rc<RefCell<Node<T>>>>;
+type NodeRef<T> = Rc<RefCell<Node<T>>>;
+type OptionalNode<T> = Option<NodeRef<T>>;
+type WeakNode<T> = Option<Weak<RefCell<Node<T>>>>;
 
-struct Node<T: Clone> {
+struct Node<T> {
   value: T,
-  next: OptionalNode<T>
+  next: OptionalNode<T>,
+  previous: WeakNode<T>,
 }
 
-pub struct LinkedList<T: Clone> {
+pub struct LinkedList<T> {
   head: OptionalNode<T>,
   tail: OptionalNode<T>,
   size: usize
 }
 
-impl<T: Clone> Node<T> {
-  fn new(value: T, next: OptionalNode<T>) -> Self {
-    Self { value, next }
+pub enum Middle<'a, T> {
+  Single(&'a T),
+  Pair(&'a T, &'a T),
+}
+
+impl<T> Node<T> {
+  fn new(value: T) -> Self {
+    Self { value, next: None, previous: None }
   }
 
   fn get_next(&self) -> OptionalNode<T> {
-    if let Some(node) = &self.next {
-      Some(Rc::clone(node))
-    } else {
-      None
-    }
+    self.next.as_ref().map(Rc::clone)
   }
 
-  fn set_next(&mut self, next: OptionalNode<T>) {
-    self.next = if let Some(next) = &next {
-      Some(Rc::clone(next))
-    } else {
-      None
-    };
+  fn get_previous(&self) -> OptionalNode<T> {
+    self.previous.as_ref().and_then(Weak::upgrade)
+  }
+
+  // Values are only mutated through `&mut` borrows of the owning list, so a
+  // shared borrow of the list guarantees that no node is mutably borrowed.
+  unsafe fn value<'a>(node: &NodeRef<T>) -> &'a T {
+    &(*node.as_ptr()).value
+  }
+
+  unsafe fn value_mut<'a>(node: &NodeRef<T>) -> &'a mut T {
+    &mut (*node.as_ptr()).value
+  }
+
+  fn into_value(node: NodeRef<T>) -> T {
+    match Rc::try_unwrap(node) {
+      Ok(node) => node.into_inner().value,
+      Err(_) => unreachable!("Detached node is still referenced"),
+    }
   }
 }
 
-impl <T: PartialEq + Clone> LinkedList<T> {
+impl<T> LinkedList<T> {
   pub fn new() -> Self {
     Self {
       head: None,
@@ -47,280 +68,1920 @@ impl <T: PartialEq + Clone> LinkedList<T> {
   }
 
   pub fn add_last(&mut self, value: T) {
-    let node = Rc::new(RefCell::new(Node::new(value, None)));
+    let node = Rc::new(RefCell::new(Node::new(value)));
 
     self.size += 1;
 
-    if let None = &self.tail {
-      self.tail = Some(Rc::clone(&node));
-      self.head = Some(node);
-
-      return;
+    match self.tail.take() {
+      Some(tail) => {
+        node.borrow_mut().previous = Some(Rc::downgrade(&tail));
+        tail.borrow_mut().next = Some(Rc::clone(&node));
+      }
+      None => self.head = Some(Rc::clone(&node)),
     }
 
-    self.tail = {
-      let mut tail_borrowed = self.tail.as_ref().unwrap().borrow_mut();
-
-      tail_borrowed.next = Some(node);
-
-      Some(Rc::clone(tail_borrowed.next.as_ref().unwrap()))
-    };
+    self.tail = Some(node);
   }
 
   pub fn add_first(&mut self, value: T) {
+    let node = Rc::new(RefCell::new(Node::new(value)));
+
     self.size += 1;
 
-    let head = if let Some(head) = &self.head {
-      Some(Rc::clone(head))
-    } else {
-      None
-    };
+    match self.head.take() {
+      Some(head) => {
+        head.borrow_mut().previous = Some(Rc::downgrade(&node));
+        node.borrow_mut().next = Some(head);
+      }
+      None => self.tail = Some(Rc::clone(&node)),
+    }
 
-    let node = Rc::new(RefCell::new(Node::new(value, head)));
+    self.head = Some(node);
+  }
 
-    if let None = &self.tail {
-      self.tail = Some(Rc::clone(&node));
+  pub fn remove_last(&mut self) -> Result<T, Error> {
+    let tail = self.tail.take().ok_or(Error::Empty)?;
+
+    match tail.borrow_mut().previous.take().and_then(|previous| previous.upgrade()) {
+      Some(previous) => {
+        previous.borrow_mut().next = None;
+        self.tail = Some(previous);
+      }
+      None => self.head = None,
     }
 
-    self.head = Some(node);
+    self.size -= 1;
+
+    Ok(Node::into_value(tail))
+  }
+
+  pub fn remove_first(&mut self) -> Result<T, Error> {
+    let head = self.pop_front_node().ok_or(Error::Empty)?;
+
+    Ok(Node::into_value(head))
+  }
+
+  pub fn peek_front(&self) -> Option<&T> {
+    self.head.as_ref().map(|head| unsafe { Node::value(head) })
   }
 
-  pub fn remove_last(&mut self) -> Result<(), &'static str> {
-    if self.size() == 1 {
-      self.head = None;
-      self.tail = None;
+  pub fn peek_back(&self) -> Option<&T> {
+    self.tail.as_ref().map(|tail| unsafe { Node::value(tail) })
+  }
 
-      self.size -= 1;
+  pub fn reverse(&mut self) {
+    let mut previous = None;
+    let mut current = self.head.take();
 
-      return Ok(())
-    }
+    self.tail = current.as_ref().map(Rc::clone);
 
-    if self.is_empty() {
-      return Err("List is empty");
+    while let Some(node) = current {
+      let mut node_borrowed = node.borrow_mut();
+
+      current = node_borrowed.next.take();
+
+      node_borrowed.previous = current.as_ref().map(Rc::downgrade);
+      node_borrowed.next = previous;
+
+      drop(node_borrowed);
+
+      previous = Some(node);
     }
 
-    let mut node_before_last = Rc::clone(&self.head.as_ref().unwrap());
+    self.head = previous;
+  }
+
+  pub fn size(&self) -> usize {
+    self.size
+  }
 
-    self.loop_items(&mut |item: &Rc<RefCell<Node<T>>>| {
-      let item_borrowed = item.borrow();
-      let next = item_borrowed.next.as_ref().unwrap();
+  pub fn is_empty(&self) -> bool {
+    self.head.is_none()
+  }
 
-      if let None = &next.borrow().next {
-        node_before_last = Rc::clone(item);
+  pub fn get(&self, index: usize) -> Option<&T> {
+    self.node_at(index).map(|node| unsafe { Node::value(&node) })
+  }
 
-        return false;
-      };
+  pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+    self.node_at(index).map(|node| unsafe { Node::value_mut(&node) })
+  }
 
-      true
-    });
+  pub fn insert_at(&mut self, index: usize, value: T) -> Result<(), Error> {
+    if index > self.size {
+      return Err(Error::OutOfBounds { index, length: self.size });
+    }
 
-    node_before_last.borrow_mut().next = None;
-    self.size -= 1;
+    if index == self.size {
+      self.add_last(value);
+    } else {
+      self.cursor_at_mut(index).insert_before(value);
+    }
 
     Ok(())
   }
 
-  pub fn remove_first(&mut self) -> Result<(), &'static str> {
-    if self.size() == 1 {
-      self.head = None;
-      self.tail = None;
+  pub fn remove_at(&mut self, index: usize) -> Result<T, Error> {
+    if index >= self.size {
+      return Err(Error::OutOfBounds { index, length: self.size });
+    }
 
-      self.size -= 1;
+    self.cursor_at_mut(index).remove_current().ok_or(Error::NodeMissing)
+  }
 
-      return Ok(())
+  pub fn split_off(&mut self, index: usize) -> Result<Self, Error> {
+    if index > self.size {
+      return Err(Error::OutOfBounds { index, length: self.size });
     }
 
-    if self.is_empty() {
-      return Err("List is empty");
+    if index == 0 {
+      return Ok(std::mem::replace(self, Self::empty()));
     }
 
-    self.head = {
-      let borrowed_head = self.head.as_ref().unwrap().borrow();
+    Ok(self.cursor_at_mut(index - 1).split_after())
+  }
 
-      Some(Rc::clone(borrowed_head.next.as_ref().unwrap()))
+  pub fn append(&mut self, other: &mut Self) {
+    let Some((first, last, size)) = other.take_chain() else {
+      return;
     };
 
-    self.size -= 1;
+    let tail = self.tail.take();
 
-    Ok(())
+    self.link_chain(tail, None, first, last);
+    self.size += size;
   }
 
-  pub fn contains(&self, value: T) -> bool {
-    self.index_of(value) > NOT_FOUND_INDEX
+  pub fn retain<F>(&mut self, mut predicate: F) where F: FnMut(&T) -> bool {
+    let mut cursor = self.cursor_front_mut();
+
+    while let Some(value) = cursor.current() {
+      if predicate(value) {
+        cursor.move_next();
+      } else {
+        cursor.remove_current();
+      }
+    }
   }
 
-  pub fn index_of(&self, value: T) -> isize {
-    let mut index = 0;
-    let mut is_found = false;
+  pub fn middle(&self) -> Option<Middle<'_, T>> {
+    let middle = self.size / 2;
+
+    if self.size % 2 == 1 {
+      return self.get(middle).map(Middle::Single);
+    }
+
+    Some(Middle::Pair(self.get(middle.checked_sub(1)?)?, self.get(middle)?))
+  }
+
+  pub fn has_loop(&self) -> bool {
+    let Some(head) = &self.head else {
+      return false;
+    };
+
+    let mut slow = Rc::clone(head);
+    let mut fast = Rc::clone(head);
 
-    self.loop_items(&mut |item: &Rc<RefCell<Node<T>>>| {
-      if item.borrow().value == value {
-        is_found = true;
+    loop {
+      let Some(next) = fast.borrow().get_next() else {
+        return false;
+      };
 
+      let Some(next_of_next) = next.borrow().get_next() else {
         return false;
+      };
+
+      fast = next_of_next;
+
+      let next_of_slow = slow.borrow().get_next().unwrap();
+
+      slow = next_of_slow;
+
+      if Rc::ptr_eq(&slow, &fast) {
+        return true;
       }
+    }
+  }
 
-      index += 1;
+  pub fn rotate_left(&mut self, k: usize) {
+    if self.size < 2 || k.is_multiple_of(self.size) {
+      return;
+    }
 
-      true
-    });
+    let mut rotated = self.split_off(k % self.size).unwrap();
 
-    if is_found {
-      index
-    } else {
-      NOT_FOUND_INDEX
+    rotated.append(self);
+
+    *self = rotated;
+  }
+
+  pub fn rotate_right(&mut self, k: usize) {
+    if self.size < 2 {
+      return;
     }
+
+    self.rotate_left(self.size - k % self.size);
   }
 
-  pub fn to_vec(&self) -> Vec<T> {
-    let mut result = Vec::with_capacity(self.size);
+  pub fn reverse_in_groups(&mut self, k: usize) {
+    if k < 2 {
+      return;
+    }
 
-    self.loop_items(&mut |item: &Rc<RefCell<Node<T>>>| {
-      result.push(item.borrow().value.clone());
+    let mut result = Self::empty();
 
-      true
-    });
+    while !self.is_empty() {
+      let rest = self.split_off(k.min(self.size)).unwrap();
 
-    result
+      self.reverse();
+      result.append(self);
+
+      *self = rest;
+    }
+
+    *self = result;
   }
 
-  pub fn reverse(&mut self) {
-    if self.is_empty() {
+  pub fn sort_by<F>(&mut self, mut compare: F) where F: FnMut(&T, &T) -> Ordering {
+    if self.size < 2 {
       return;
     }
 
-    let mut current = Rc::clone(self.head.as_ref().unwrap());
-    let mut previous = None;
+    self.tail = None;
 
-    loop {
-      current = {
-        let next = current.borrow().get_next();
+    let head = self.head.take().unwrap();
 
-        current.borrow_mut().set_next(previous);
+    self.head = Some(Self::merge_sort(head, self.size, &mut compare));
+    self.relink_previous();
+  }
 
-        if next.is_none() {
-          break;
-        }
+  pub fn sort_by_key<K, F>(&mut self, mut key: F) where K: Ord, F: FnMut(&T) -> K {
+    self.sort_by(|left, right| key(left).cmp(&key(right)));
+  }
 
-        previous = Some(Rc::clone(&current));
+  pub fn iter(&self) -> Iter<'_, T> {
+    Iter {
+      front: self.head.as_ref().map(Rc::clone),
+      back: self.tail.as_ref().map(Rc::clone),
+      remaining: self.size,
+      marker: PhantomData,
+    }
+  }
 
-        Rc::clone(next.as_ref().unwrap())
-      };
+  pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+    IterMut {
+      front: self.head.as_ref().map(Rc::clone),
+      back: self.tail.as_ref().map(Rc::clone),
+      remaining: self.size,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn cursor_front(&self) -> Cursor<'_, T> {
+    Cursor { current: self.head.as_ref().map(Rc::clone), index: 0, list: self }
+  }
+
+  pub fn cursor_back(&self) -> Cursor<'_, T> {
+    Cursor { current: self.tail.as_ref().map(Rc::clone), index: self.size.saturating_sub(1), list: self }
+  }
+
+  pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+    CursorMut { current: self.head.as_ref().map(Rc::clone), index: 0, list: self }
+  }
+
+  pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+    CursorMut { current: self.tail.as_ref().map(Rc::clone), index: self.size.saturating_sub(1), list: self }
+  }
+
+  fn node_at(&self, index: usize) -> OptionalNode<T> {
+    if index >= self.size {
+      return None;
     }
 
-    let head = Rc::clone(self.head.as_ref().unwrap());
-    let tail = Rc::clone(self.tail.as_ref().unwrap());
+    if index < self.size / 2 {
+      let mut current = self.head.as_ref().map(Rc::clone);
+
+      for _ in 0..index {
+        current = current.and_then(|node| node.borrow().get_next());
+      }
+
+      current
+    } else {
+      let mut current = self.tail.as_ref().map(Rc::clone);
+
+      for _ in index..self.size - 1 {
+        current = current.and_then(|node| node.borrow().get_previous());
+      }
+
+      current
+    }
+  }
 
-    self.tail = Some(head);
-    self.head = Some(tail);
+  fn cursor_at_mut(&mut self, index: usize) -> CursorMut<'_, T> {
+    CursorMut { current: self.node_at(index), index, list: self }
   }
 
-  pub fn get_kth_from_end(&self, k: usize) -> Result<T, &'static str> {
-    if k <= 0 {
-      return Err("K must be greater than zero");
+  fn merge_sort<F>(head: NodeRef<T>, length: usize, compare: &mut F) -> NodeRef<T> where F: FnMut(&T, &T) -> Ordering {
+    if length < 2 {
+      return head;
     }
 
-    if k > self.size() {
-      return Err("K must be less than or equal to size of the list");
+    let middle = length / 2;
+    let mut last_of_left = Rc::clone(&head);
+
+    for _ in 1..middle {
+      let next = last_of_left.borrow().get_next().unwrap();
+
+      last_of_left = next;
     }
 
-    let mut result = Rc::clone(self.head.as_ref().unwrap());
-    let mut end = Rc::clone(&result);
+    let right = last_of_left.borrow_mut().next.take().unwrap();
 
-    let distance = k - 1;
+    let left = Self::merge_sort(head, middle, compare);
+    let right = Self::merge_sort(right, length - middle, compare);
 
-    for _ in 0..distance {
-      end = {
-        let end_borrowed = end.borrow();
+    Self::merge_chains(Some(left), Some(right), compare).unwrap()
+  }
 
-        end_borrowed.get_next().unwrap()
-      };
+  fn merge_chains<F>(mut left: OptionalNode<T>, mut right: OptionalNode<T>, compare: &mut F) -> OptionalNode<T> where F: FnMut(&T, &T) -> Ordering {
+    let mut head: OptionalNode<T> = None;
+    let mut tail: OptionalNode<T> = None;
+
+    while let (Some(left_node), Some(right_node)) = (&left, &right) {
+      let take_left = compare(&left_node.borrow().value, &right_node.borrow().value) != Ordering::Greater;
+
+      let source = if take_left { &mut left } else { &mut right };
+      let node = source.take().unwrap();
+
+      *source = node.borrow_mut().next.take();
+
+      match &tail {
+        Some(tail) => tail.borrow_mut().next = Some(Rc::clone(&node)),
+        None => head = Some(Rc::clone(&node)),
+      }
+
+      tail = Some(node);
     }
 
-    self.loop_items(&mut |item: &Rc<RefCell<Node<T>>>| {
-      end = {
-        let end_borrowed = end.borrow();
+    let rest = left.or(right);
 
-        if end_borrowed.next.is_none() {
-          result = Rc::clone(item);
+    match &tail {
+      Some(tail) => tail.borrow_mut().next = rest,
+      None => head = rest,
+    }
 
-          return false;
-        }
+    head
+  }
 
-        end_borrowed.get_next().unwrap()
-      };
+  fn relink_previous(&mut self) {
+    let mut previous: OptionalNode<T> = None;
+    let mut current = self.head.as_ref().map(Rc::clone);
 
-      true
-    });
+    while let Some(node) = current {
+      node.borrow_mut().previous = previous.as_ref().map(Rc::downgrade);
+      current = node.borrow().get_next();
+      previous = Some(node);
+    }
 
-    let result_borrowed = result.borrow();
+    self.tail = previous;
+  }
 
-    Ok(result_borrowed.value.clone())
+  fn pop_front_node(&mut self) -> OptionalNode<T> {
+    let head = self.head.take()?;
+
+    match head.borrow_mut().next.take() {
+      Some(next) => {
+        next.borrow_mut().previous = None;
+        self.head = Some(next);
+      }
+      None => self.tail = None,
+    }
+
+    self.size -= 1;
+
+    Some(head)
   }
 
-  pub fn size(&self) -> usize {
-    self.size
+  fn push_back_node(&mut self, node: NodeRef<T>) {
+    let tail = self.tail.take();
+
+    self.link_chain(tail, None, Rc::clone(&node), node);
+    self.size += 1;
   }
 
-  pub fn is_empty(&self) -> bool {
-    self.head.is_none()
+  fn empty() -> Self {
+    Self { head: None, tail: None, size: 0 }
   }
 
-  fn loop_items<F>(&self, mut consumer: F) where F: FnMut(&Rc<RefCell<Node<T>>>) -> bool {
-    if let Some(head) = &self.head {
-      let mut current = Rc::clone(head);
+  fn link_chain(&mut self, previous: OptionalNode<T>, next: OptionalNode<T>, first: NodeRef<T>, last: NodeRef<T>) {
+    match previous {
+      Some(previous) => {
+        first.borrow_mut().previous = Some(Rc::downgrade(&previous));
+        previous.borrow_mut().next = Some(first);
+      }
+      None => {
+        first.borrow_mut().previous = None;
+        self.head = Some(first);
+      }
+    }
 
-      loop {
-        current = {
-          if !consumer(&current) {
-            break;
-          }
+    match next {
+      Some(next) => {
+        next.borrow_mut().previous = Some(Rc::downgrade(&last));
+        last.borrow_mut().next = Some(next);
+      }
+      None => {
+        last.borrow_mut().next = None;
+        self.tail = Some(last);
+      }
+    }
+  }
 
-          let current_borrowed = current.borrow();
+  fn take_chain(&mut self) -> Option<(NodeRef<T>, NodeRef<T>, usize)> {
+    let size = std::mem::take(&mut self.size);
 
-          if let Some(next) = &current_borrowed.next {
-            Rc::clone(&next)
-          } else {
-            break;
-          }
-        }
+    match (self.head.take(), self.tail.take()) {
+      (Some(head), Some(tail)) => Some((head, tail, size)),
+      _ => None,
+    }
+  }
+}
+
+impl<T: PartialEq> LinkedList<T> {
+  pub fn contains(&self, value: &T) -> bool {
+    self.index_of(value).is_some()
+  }
+
+  pub fn index_of(&self, value: &T) -> Option<usize> {
+    self.iter().position(|item| item == value)
+  }
+
+  pub fn remove(&mut self, value: &T) -> Option<T> {
+    let index = self.index_of(value)?;
+
+    self.cursor_at_mut(index).remove_current()
+  }
+
+  pub fn is_palindrome(&self) -> bool {
+    self.iter().zip(self.iter().rev()).take(self.size / 2).all(|(front, back)| front == back)
+  }
+
+  pub fn dedup(&mut self) {
+    let mut cursor = self.cursor_front_mut();
+
+    cursor.move_next();
+
+    while cursor.index().is_some() {
+      let is_duplicate = {
+        let view = cursor.as_cursor();
+
+        view.current() == view.peek_prev()
+      };
+
+      if is_duplicate {
+        cursor.remove_current();
+      } else {
+        cursor.move_next();
       }
     }
   }
 }
 
-impl<T: Display + Clone + PartialEq> Display for LinkedList<T> {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    let mut formatted_items = String::new();
+impl<T: Eq + Hash> LinkedList<T> {
+  pub fn remove_duplicates(&mut self) {
+    let should_keep: Vec<bool> = {
+      let mut seen = HashSet::with_capacity(self.size);
+
+      self.iter().map(|value| seen.insert(value)).collect()
+    };
 
-    self.loop_items(&mut |item: &Rc<RefCell<Node<T>>>| {
-      let delimeter = if let Some(_) = item.borrow().next { ", " } else { "" };
+    let mut index = 0;
 
-      formatted_items.push_str(&(item.borrow().value.to_string() + delimeter));
+    self.retain(|_| {
+      index += 1;
 
-      true
+      should_keep[index - 1]
     });
+  }
+}
 
-    write!(f, "[{}]", formatted_items)?;
+impl<T: Ord> LinkedList<T> {
+  pub fn partition_around(&mut self, pivot: &T) {
+    let mut less = Self::empty();
+    let mut equal = Self::empty();
+    let mut greater = Self::empty();
 
-    Ok(())
+    while let Some(node) = self.pop_front_node() {
+      let order = node.borrow().value.cmp(pivot);
+
+      match order {
+        Ordering::Less => less.push_back_node(node),
+        Ordering::Equal => equal.push_back_node(node),
+        Ordering::Greater => greater.push_back_node(node),
+      }
+    }
+
+    less.append(&mut equal);
+    less.append(&mut greater);
+
+    *self = less;
   }
-}
 
-impl<T: Clone> Drop for LinkedList<T> {
-  fn drop(&mut self) {
-    if let Some(mut child) = self.head.take() {
-      loop {
-        child = {
-          let mut child_borrowed = child.borrow_mut();
+  pub fn sort(&mut self) {
+    self.sort_by(T::cmp);
+  }
 
-          if let Some(next) = child_borrowed.next.take() {
-            next
-          } else {
-            break;
-          }
-        };
+  pub fn merge_sorted(&mut self, mut other: Self) {
+    let Some((first, _, size)) = other.take_chain() else {
+      return;
+    };
+
+    self.tail = None;
+
+    let head = self.head.take();
+
+    self.head = Self::merge_chains(head, Some(first), &mut T::cmp);
+    self.size += size;
+    self.relink_previous();
+  }
+
+  pub fn insert_sorted(&mut self, value: T) {
+    let mut cursor = self.cursor_front_mut();
+
+    while let Some(current) = cursor.current() {
+      if *current > value {
+        break;
       }
+
+      cursor.move_next();
+    }
+
+    cursor.insert_before(value);
+  }
+}
+
+impl<T: PartialOrd> LinkedList<T> {
+  pub fn is_sorted(&self) -> bool {
+    self.iter().zip(self.iter().skip(1)).all(|(previous, next)| previous <= next)
+  }
+}
+
+impl<T: Clone> LinkedList<T> {
+  pub fn to_vec(&self) -> Vec<T> {
+    self.iter().cloned().collect()
+  }
+
+  pub fn get_kth_from_end(&self, k: usize) -> Result<T, Error> {
+    if k == 0 || k > self.size() {
+      return Err(Error::OutOfBounds { index: k, length: self.size() });
+    }
+
+    let mut result = Rc::clone(self.tail.as_ref().unwrap());
+
+    for _ in 1..k {
+      result = {
+        let result_borrowed = result.borrow();
+
+        result_borrowed.get_previous().unwrap()
+      };
     }
+
+    let result_borrowed = result.borrow();
+
+    Ok(result_borrowed.value.clone())
+  }
+}
+
+impl<T> Default for LinkedList<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Display> Display for LinkedList<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut formatted_items = String::new();
+
+    for (index, item) in self.iter().enumerate() {
+      let delimeter = if index == self.size - 1 { "" } else { ", " };
+
+      formatted_items.push_str(&(item.to_string() + delimeter));
+    }
+
+    write!(f, "[{}]", formatted_items)?;
+
+    Ok(())
+  }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+  fn clone(&self) -> Self {
+    self.iter().cloned().collect()
+  }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.size == other.size && self.iter().eq(other.iter())
+  }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T> Extend<T> for LinkedList<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for value in iter {
+      self.add_last(value);
+    }
+  }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut list = Self::new();
+
+    list.extend(iter);
+
+    list
+  }
+}
+
+impl<T> Drop for LinkedList<T> {
+  fn drop(&mut self) {
+    self.tail = None;
+
+    let mut current = self.head.take();
+
+    while let Some(node) = current {
+      current = node.borrow_mut().next.take();
+    }
+  }
+}
+
+pub struct Iter<'a, T> {
+  front: OptionalNode<T>,
+  back: OptionalNode<T>,
+  remaining: usize,
+  marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<&'a T> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    let node = self.front.take()?;
+
+    self.front = node.borrow().get_next();
+    self.remaining -= 1;
+
+    Some(unsafe { Node::value(&node) })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+  fn next_back(&mut self) -> Option<&'a T> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    let node = self.back.take()?;
+
+    self.back = node.borrow().get_previous();
+    self.remaining -= 1;
+
+    Some(unsafe { Node::value(&node) })
+  }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+  front: OptionalNode<T>,
+  back: OptionalNode<T>,
+  remaining: usize,
+  marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+  type Item = &'a mut T;
+
+  fn next(&mut self) -> Option<&'a mut T> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    let node = self.front.take()?;
+
+    self.front = node.borrow().get_next();
+    self.remaining -= 1;
+
+    Some(unsafe { Node::value_mut(&node) })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+  fn next_back(&mut self) -> Option<&'a mut T> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    let node = self.back.take()?;
+
+    self.back = node.borrow().get_previous();
+    self.remaining -= 1;
+
+    Some(unsafe { Node::value_mut(&node) })
+  }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T> {
+  list: LinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.list.remove_first().ok()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.list.size, Some(self.list.size))
+  }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+  fn next_back(&mut self) -> Option<T> {
+    self.list.remove_last().ok()
+  }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedList<T> {
+  type Item = T;
+  type IntoIter = IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter { list: self }
+  }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+  type Item = &'a T;
+  type IntoIter = Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+  type Item = &'a mut T;
+  type IntoIter = IterMut<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+pub struct Cursor<'a, T> {
+  list: &'a LinkedList<T>,
+  current: OptionalNode<T>,
+  index: usize,
+}
+
+impl<'a, T> Cursor<'a, T> {
+  pub fn index(&self) -> Option<usize> {
+    self.current.as_ref().map(|_| self.index)
+  }
+
+  pub fn current(&self) -> Option<&'a T> {
+    self.current.as_ref().map(|node| unsafe { Node::value(node) })
+  }
+
+  pub fn peek_next(&self) -> Option<&'a T> {
+    let next = match &self.current {
+      Some(node) => node.borrow().get_next(),
+      None => self.list.head.as_ref().map(Rc::clone),
+    };
+
+    next.map(|node| unsafe { Node::value(&node) })
+  }
+
+  pub fn peek_prev(&self) -> Option<&'a T> {
+    let previous = match &self.current {
+      Some(node) => node.borrow().get_previous(),
+      None => self.list.tail.as_ref().map(Rc::clone),
+    };
+
+    previous.map(|node| unsafe { Node::value(&node) })
+  }
+
+  pub fn move_next(&mut self) {
+    (self.current, self.index) = match self.current.take() {
+      Some(node) => (node.borrow().get_next(), self.index + 1),
+      None => (self.list.head.as_ref().map(Rc::clone), 0),
+    };
+
+    if self.current.is_none() {
+      self.index = self.list.size;
+    }
+  }
+
+  pub fn move_prev(&mut self) {
+    (self.current, self.index) = match self.current.take() {
+      Some(node) => (node.borrow().get_previous(), self.index.wrapping_sub(1)),
+      None => (self.list.tail.as_ref().map(Rc::clone), self.list.size.wrapping_sub(1)),
+    };
+
+    if self.current.is_none() {
+      self.index = self.list.size;
+    }
+  }
+}
+
+pub struct CursorMut<'a, T> {
+  list: &'a mut LinkedList<T>,
+  current: OptionalNode<T>,
+  index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+  pub fn index(&self) -> Option<usize> {
+    self.current.as_ref().map(|_| self.index)
+  }
+
+  pub fn current(&mut self) -> Option<&mut T> {
+    self.current.as_ref().map(|node| unsafe { Node::value_mut(node) })
+  }
+
+  pub fn peek_next(&mut self) -> Option<&mut T> {
+    let next = match &self.current {
+      Some(node) => node.borrow().get_next(),
+      None => self.list.head.as_ref().map(Rc::clone),
+    };
+
+    next.map(|node| unsafe { Node::value_mut(&node) })
+  }
+
+  pub fn peek_prev(&mut self) -> Option<&mut T> {
+    let previous = match &self.current {
+      Some(node) => node.borrow().get_previous(),
+      None => self.list.tail.as_ref().map(Rc::clone),
+    };
+
+    previous.map(|node| unsafe { Node::value_mut(&node) })
+  }
+
+  pub fn as_cursor(&self) -> Cursor<'_, T> {
+    Cursor { list: self.list, current: self.current.as_ref().map(Rc::clone), index: self.index }
+  }
+
+  pub fn move_next(&mut self) {
+    (self.current, self.index) = match self.current.take() {
+      Some(node) => (node.borrow().get_next(), self.index + 1),
+      None => (self.list.head.as_ref().map(Rc::clone), 0),
+    };
+
+    if self.current.is_none() {
+      self.index = self.list.size;
+    }
+  }
+
+  pub fn move_prev(&mut self) {
+    (self.current, self.index) = match self.current.take() {
+      Some(node) => (node.borrow().get_previous(), self.index.wrapping_sub(1)),
+      None => (self.list.tail.as_ref().map(Rc::clone), self.list.size.wrapping_sub(1)),
+    };
+
+    if self.current.is_none() {
+      self.index = self.list.size;
+    }
+  }
+
+  pub fn insert_after(&mut self, value: T) {
+    let node = Rc::new(RefCell::new(Node::new(value)));
+
+    let (previous, next) = self.neighbours_after();
+
+    self.list.link_chain(previous, next, Rc::clone(&node), node);
+    self.list.size += 1;
+
+    if self.current.is_none() {
+      self.index = self.list.size;
+    }
+  }
+
+  pub fn insert_before(&mut self, value: T) {
+    let node = Rc::new(RefCell::new(Node::new(value)));
+
+    let (previous, next) = self.neighbours_before();
+
+    self.list.link_chain(previous, next, Rc::clone(&node), node);
+    self.list.size += 1;
+    self.index += 1;
+  }
+
+  pub fn remove_current(&mut self) -> Option<T> {
+    let node = self.current.take()?;
+
+    let (previous, next) = {
+      let mut node_borrowed = node.borrow_mut();
+
+      (node_borrowed.previous.take().and_then(|previous| previous.upgrade()), node_borrowed.next.take())
+    };
+
+    match &previous {
+      Some(previous) => previous.borrow_mut().next = next.as_ref().map(Rc::clone),
+      None => self.list.head = next.as_ref().map(Rc::clone),
+    }
+
+    match &next {
+      Some(next) => next.borrow_mut().previous = previous.as_ref().map(Rc::downgrade),
+      None => self.list.tail = previous,
+    }
+
+    self.list.size -= 1;
+    self.current = next;
+
+    if self.current.is_none() {
+      self.index = self.list.size;
+    }
+
+    Some(Node::into_value(node))
+  }
+
+  pub fn split_after(&mut self) -> LinkedList<T> {
+    let Some(node) = &self.current else {
+      return std::mem::replace(self.list, LinkedList::empty());
+    };
+
+    let Some(next) = node.borrow_mut().next.take() else {
+      return LinkedList::empty();
+    };
+
+    next.borrow_mut().previous = None;
+
+    let size = self.list.size - self.index - 1;
+
+    self.list.size = self.index + 1;
+
+    LinkedList { head: Some(next), tail: self.list.tail.replace(Rc::clone(node)), size }
+  }
+
+  pub fn split_before(&mut self) -> LinkedList<T> {
+    let Some(node) = &self.current else {
+      let split = std::mem::replace(self.list, LinkedList::empty());
+
+      self.index = 0;
+
+      return split;
+    };
+
+    let Some(previous) = node.borrow_mut().previous.take().and_then(|previous| previous.upgrade()) else {
+      return LinkedList::empty();
+    };
+
+    previous.borrow_mut().next = None;
+
+    let size = self.index;
+
+    self.list.size -= size;
+    self.index = 0;
+
+    LinkedList { head: self.list.head.replace(Rc::clone(node)), tail: Some(previous), size }
+  }
+
+  pub fn splice_after(&mut self, mut other: LinkedList<T>) {
+    let Some((first, last, size)) = other.take_chain() else {
+      return;
+    };
+
+    let (previous, next) = self.neighbours_after();
+
+    self.list.link_chain(previous, next, first, last);
+    self.list.size += size;
+
+    if self.current.is_none() {
+      self.index = self.list.size;
+    }
+  }
+
+  pub fn splice_before(&mut self, mut other: LinkedList<T>) {
+    let Some((first, last, size)) = other.take_chain() else {
+      return;
+    };
+
+    let (previous, next) = self.neighbours_before();
+
+    self.list.link_chain(previous, next, first, last);
+    self.list.size += size;
+    self.index += size;
+  }
+
+  fn neighbours_after(&self) -> (OptionalNode<T>, OptionalNode<T>) {
+    match &self.current {
+      Some(node) => (Some(Rc::clone(node)), node.borrow().get_next()),
+      None => (None, self.list.head.as_ref().map(Rc::clone)),
+    }
+  }
+
+  fn neighbours_before(&self) -> (OptionalNode<T>, OptionalNode<T>) {
+    match &self.current {
+      Some(node) => (node.borrow().get_previous(), Some(Rc::clone(node))),
+      None => (self.list.tail.as_ref().map(Rc::clone), None),
+    }
+  }
+}
+
+type SharedNodeRef<T> = Arc<SharedNode<T>>;
+
+struct SharedNode<T> {
+  value: T,
+  next: RefCell<Option<SharedNodeRef<T>>>,
+  previous: RefCell<Option<sync::Weak<SharedNode<T>>>>,
+}
+
+// Links are only touched while holding the list's `ends` lock, or after a node has been detached from it.
+unsafe impl<T: Send> Send for SharedNode<T> {}
+unsafe impl<T: Send> Sync for SharedNode<T> {}
+
+struct SharedEnds<T> {
+  head: Option<SharedNodeRef<T>>,
+  tail: Option<SharedNodeRef<T>>,
+  size: usize,
+}
+
+pub struct SharedLinkedList<T> {
+  ends: Mutex<SharedEnds<T>>,
+}
+
+impl<T> SharedNode<T> {
+  fn new(value: T) -> SharedNodeRef<T> {
+    Arc::new(Self { value, next: RefCell::new(None), previous: RefCell::new(None) })
+  }
+
+  fn into_value(node: SharedNodeRef<T>) -> T {
+    match Arc::try_unwrap(node) {
+      Ok(node) => node.value,
+      Err(_) => unreachable!("Detached node is still referenced"),
+    }
+  }
+}
+
+impl<T> SharedLinkedList<T> {
+  pub fn new() -> Self {
+    Self {
+      ends: Mutex::new(SharedEnds { head: None, tail: None, size: 0 }),
+    }
+  }
+
+  pub fn add_last(&self, value: T) {
+    let node = SharedNode::new(value);
+    let mut ends = self.lock_ends();
+
+    match ends.tail.take() {
+      Some(tail) => {
+        *node.previous.borrow_mut() = Some(Arc::downgrade(&tail));
+        *tail.next.borrow_mut() = Some(Arc::clone(&node));
+      }
+      None => ends.head = Some(Arc::clone(&node)),
+    }
+
+    ends.tail = Some(node);
+    ends.size += 1;
+  }
+
+  pub fn add_first(&self, value: T) {
+    let node = SharedNode::new(value);
+    let mut ends = self.lock_ends();
+
+    match ends.head.take() {
+      Some(head) => {
+        *head.previous.borrow_mut() = Some(Arc::downgrade(&node));
+        *node.next.borrow_mut() = Some(head);
+      }
+      None => ends.tail = Some(Arc::clone(&node)),
+    }
+
+    ends.head = Some(node);
+    ends.size += 1;
+  }
+
+  pub fn remove_first(&self) -> Result<T, Error> {
+    let mut ends = self.lock_ends();
+    let head = ends.head.take().ok_or(Error::Empty)?;
+
+    let next = head.next.borrow_mut().take();
+
+    match next {
+      Some(next) => {
+        *next.previous.borrow_mut() = None;
+        ends.head = Some(next);
+      }
+      None => ends.tail = None,
+    }
+
+    ends.size -= 1;
+
+    drop(ends);
+
+    Ok(SharedNode::into_value(head))
+  }
+
+  pub fn remove_last(&self) -> Result<T, Error> {
+    let mut ends = self.lock_ends();
+    let tail = ends.tail.take().ok_or(Error::Empty)?;
+
+    let previous = tail.previous.borrow_mut().take().and_then(|previous| previous.upgrade());
+
+    match previous {
+      Some(previous) => {
+        *previous.next.borrow_mut() = None;
+        ends.tail = Some(previous);
+      }
+      None => ends.head = None,
+    }
+
+    ends.size -= 1;
+
+    drop(ends);
+
+    Ok(SharedNode::into_value(tail))
+  }
+
+  pub fn size(&self) -> usize {
+    self.lock_ends().size
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.lock_ends().head.is_none()
+  }
+
+  pub fn clear(&self) {
+    let mut ends = self.lock_ends();
+
+    ends.tail = None;
+    ends.size = 0;
+
+    let mut current = ends.head.take();
+
+    drop(ends);
+
+    while let Some(node) = current {
+      current = node.next.borrow_mut().take();
+    }
+  }
+
+  fn for_each_node<F>(&self, mut consumer: F) where F: FnMut(&T) -> bool {
+    let ends = self.lock_ends();
+    let mut current = ends.head.as_ref().map(Arc::clone);
+
+    while let Some(node) = current {
+      if !consumer(&node.value) {
+        break;
+      }
+
+      current = node.next.borrow().as_ref().map(Arc::clone);
+    }
+  }
+
+  fn lock_ends(&self) -> MutexGuard<'_, SharedEnds<T>> {
+    self.ends.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+impl<T: Clone> SharedLinkedList<T> {
+  pub fn peek_front(&self) -> Option<T> {
+    let ends = self.lock_ends();
+
+    ends.head.as_ref().map(|head| head.value.clone())
+  }
+
+  pub fn peek_back(&self) -> Option<T> {
+    let ends = self.lock_ends();
+
+    ends.tail.as_ref().map(|tail| tail.value.clone())
+  }
+
+  pub fn to_vec(&self) -> Vec<T> {
+    let mut result = Vec::new();
+
+    self.for_each_node(|value| {
+      result.push(value.clone());
+
+      true
+    });
+
+    result
+  }
+}
+
+impl<T: PartialEq> SharedLinkedList<T> {
+  pub fn contains(&self, value: &T) -> bool {
+    let mut is_found = false;
+
+    self.for_each_node(|item| {
+      is_found = item == value;
+
+      !is_found
+    });
+
+    is_found
+  }
+}
+
+impl<T> Default for SharedLinkedList<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> Drop for SharedLinkedList<T> {
+  fn drop(&mut self) {
+    self.clear();
+  }
+}
+
+pub struct CircularList<T> {
+  current: OptionalNode<T>,
+  size: usize,
+}
+
+impl<T> CircularList<T> {
+  pub fn new() -> Self {
+    Self { current: None, size: 0 }
+  }
+
+  pub fn insert(&mut self, value: T) {
+    let node = Rc::new(RefCell::new(Node::new(value)));
+
+    self.size += 1;
+
+    let Some(current) = &self.current else {
+      {
+        let mut node_borrowed = node.borrow_mut();
+
+        node_borrowed.next = Some(Rc::clone(&node));
+        node_borrowed.previous = Some(Rc::downgrade(&node));
+      }
+
+      self.current = Some(node);
+
+      return;
+    };
+
+    let previous = current.borrow().get_previous().unwrap();
+
+    {
+      let mut node_borrowed = node.borrow_mut();
+
+      node_borrowed.next = Some(Rc::clone(current));
+      node_borrowed.previous = Some(Rc::downgrade(&previous));
+    }
+
+    current.borrow_mut().previous = Some(Rc::downgrade(&node));
+    previous.borrow_mut().next = Some(node);
+  }
+
+  pub fn current(&self) -> Option<&T> {
+    self.current.as_ref().map(|node| unsafe { Node::value(node) })
+  }
+
+  pub fn current_mut(&mut self) -> Option<&mut T> {
+    self.current.as_ref().map(|node| unsafe { Node::value_mut(node) })
+  }
+
+  pub fn rotate(&mut self) {
+    self.current = self.current.take().and_then(|node| node.borrow().get_next());
+  }
+
+  pub fn rotate_back(&mut self) {
+    self.current = self.current.take().and_then(|node| node.borrow().get_previous());
+  }
+
+  pub fn remove_current(&mut self) -> Option<T> {
+    let node = self.current.take()?;
+
+    let (previous, next) = {
+      let mut node_borrowed = node.borrow_mut();
+
+      (node_borrowed.previous.take().and_then(|previous| previous.upgrade()), node_borrowed.next.take())
+    };
+
+    self.size -= 1;
+
+    if self.size > 0 {
+      let previous = previous.unwrap();
+      let next = next.unwrap();
+
+      next.borrow_mut().previous = Some(Rc::downgrade(&previous));
+      previous.borrow_mut().next = Some(Rc::clone(&next));
+
+      self.current = Some(next);
+    } else {
+      drop((previous, next));
+    }
+
+    Some(Node::into_value(node))
+  }
+
+  pub fn len(&self) -> usize {
+    self.size
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.size == 0
+  }
+
+  pub fn iter(&self) -> Iter<'_, T> {
+    Iter {
+      front: self.current.as_ref().map(Rc::clone),
+      back: self.current.as_ref().and_then(|node| node.borrow().get_previous()),
+      remaining: self.size,
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<T> Default for CircularList<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Debug> Debug for CircularList<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Extend<T> for CircularList<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for value in iter {
+      self.insert(value);
+    }
+  }
+}
+
+impl<T> FromIterator<T> for CircularList<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut list = Self::new();
+
+    list.extend(iter);
+
+    list
+  }
+}
+
+impl<T> Drop for CircularList<T> {
+  fn drop(&mut self) {
+    let Some(current) = self.current.take() else {
+      return;
+    };
+
+    let mut next = current.borrow_mut().next.take();
+
+    while let Some(node) = next {
+      next = node.borrow_mut().next.take();
+    }
+  }
+}
+
+const SKIP_LIST_MAX_LEVEL: usize = 32;
+
+type SkipNodeRef<T> = Rc<RefCell<SkipNode<T>>>;
+type OptionalSkipNode<T> = Option<SkipNodeRef<T>>;
+
+struct SkipNode<T> {
+  value: T,
+  forward: Vec<OptionalSkipNode<T>>,
+}
+
+impl<T> SkipNode<T> {
+  unsafe fn value<'a>(node: &SkipNodeRef<T>) -> &'a T {
+    &(*node.as_ptr()).value
+  }
+}
+
+pub struct SkipListRng {
+  state: u64,
+}
+
+impl SkipListRng {
+  pub fn with_seed(seed: u64) -> Self {
+    Self { state: seed.max(1) }
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state ^= self.state << 13;
+    self.state ^= self.state >> 7;
+    self.state ^= self.state << 17;
+
+    self.state
+  }
+
+  fn next_level(&mut self) -> usize {
+    (self.next_u64().trailing_ones() as usize + 1).min(SKIP_LIST_MAX_LEVEL)
+  }
+}
+
+pub struct SkipList<T> {
+  head: Vec<OptionalSkipNode<T>>,
+  size: usize,
+  rng: SkipListRng,
+}
+
+impl<T: Ord> SkipList<T> {
+  pub fn new() -> Self {
+    let seed = RandomState::new().build_hasher().finish();
+
+    Self::with_seed(seed)
+  }
+
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      head: vec![None; SKIP_LIST_MAX_LEVEL],
+      size: 0,
+      rng: SkipListRng::with_seed(seed),
+    }
+  }
+
+  pub fn insert(&mut self, value: T) -> bool {
+    let predecessors = self.find_predecessors(&value);
+
+    if let Some(next) = self.forward(&predecessors[0], 0) {
+      if next.borrow().value == value {
+        return false;
+      }
+    }
+
+    let level = self.rng.next_level();
+    let node = Rc::new(RefCell::new(SkipNode { value, forward: vec![None; level] }));
+
+    for (index, predecessor) in predecessors.iter().enumerate().take(level) {
+      let next = self.forward(predecessor, index);
+
+      node.borrow_mut().forward[index] = next;
+
+      self.set_forward(predecessor, index, Some(Rc::clone(&node)));
+    }
+
+    self.size += 1;
+
+    true
+  }
+
+  pub fn contains(&self, value: &T) -> bool {
+    self.get(value).is_some()
+  }
+
+  pub fn get(&self, value: &T) -> Option<&T> {
+    let predecessors = self.find_predecessors(value);
+    let next = self.forward(&predecessors[0], 0)?;
+
+    if next.borrow().value != *value {
+      return None;
+    }
+
+    Some(unsafe { SkipNode::value(&next) })
+  }
+
+  pub fn remove(&mut self, value: &T) -> Option<T> {
+    let predecessors = self.find_predecessors(value);
+    let node = self.forward(&predecessors[0], 0)?;
+
+    if node.borrow().value != *value {
+      return None;
+    }
+
+    let forward = std::mem::take(&mut node.borrow_mut().forward);
+
+    for (index, next) in forward.into_iter().enumerate() {
+      self.set_forward(&predecessors[index], index, next);
+    }
+
+    self.size -= 1;
+
+    match Rc::try_unwrap(node) {
+      Ok(node) => Some(node.into_inner().value),
+      Err(_) => unreachable!("Detached node is still referenced"),
+    }
+  }
+
+  pub fn first(&self) -> Option<&T> {
+    self.head[0].as_ref().map(|node| unsafe { SkipNode::value(node) })
+  }
+
+  pub fn range<R: RangeBounds<T>>(&self, range: R) -> SkipListRange<'_, T, R> {
+    let current = match range.start_bound() {
+      Bound::Included(start) | Bound::Excluded(start) => {
+        let predecessors = self.find_predecessors(start);
+        let mut current = self.forward(&predecessors[0], 0);
+
+        if let (Bound::Excluded(start), Some(node)) = (range.start_bound(), &current) {
+          if node.borrow().value == *start {
+            let next = node.borrow().forward[0].as_ref().map(Rc::clone);
+
+            current = next;
+          }
+        }
+
+        current
+      }
+      Bound::Unbounded => self.head[0].as_ref().map(Rc::clone),
+    };
+
+    SkipListRange { current, range, marker: PhantomData }
+  }
+
+  fn find_predecessors(&self, value: &T) -> Vec<OptionalSkipNode<T>> {
+    let mut predecessors = vec![None; SKIP_LIST_MAX_LEVEL];
+    let mut current: OptionalSkipNode<T> = None;
+
+    for level in (0..SKIP_LIST_MAX_LEVEL).rev() {
+      while let Some(next) = self.forward(&current, level) {
+        if next.borrow().value >= *value {
+          break;
+        }
+
+        current = Some(next);
+      }
+
+      predecessors[level] = current.as_ref().map(Rc::clone);
+    }
+
+    predecessors
+  }
+}
+
+impl<T> SkipList<T> {
+  pub fn len(&self) -> usize {
+    self.size
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.size == 0
+  }
+
+  pub fn iter(&self) -> SkipListRange<'_, T, RangeFull> {
+    SkipListRange {
+      current: self.head[0].as_ref().map(Rc::clone),
+      range: ..,
+      marker: PhantomData,
+    }
+  }
+
+  fn forward(&self, node: &OptionalSkipNode<T>, level: usize) -> OptionalSkipNode<T> {
+    match node {
+      Some(node) => node.borrow().forward[level].as_ref().map(Rc::clone),
+      None => self.head[level].as_ref().map(Rc::clone),
+    }
+  }
+
+  fn set_forward(&mut self, node: &OptionalSkipNode<T>, level: usize, next: OptionalSkipNode<T>) {
+    match node {
+      Some(node) => node.borrow_mut().forward[level] = next,
+      None => self.head[level] = next,
+    }
+  }
+}
+
+impl<T: Ord> Default for SkipList<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Ord + Debug> Debug for SkipList<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Drop for SkipList<T> {
+  fn drop(&mut self) {
+    let mut current = self.head[0].take();
+
+    self.head.clear();
+
+    while let Some(node) = current {
+      let mut node_borrowed = node.borrow_mut();
+
+      current = node_borrowed.forward[0].take();
+      node_borrowed.forward.clear();
+    }
+  }
+}
+
+pub struct SkipListRange<'a, T, R> {
+  current: OptionalSkipNode<T>,
+  range: R,
+  marker: PhantomData<&'a T>,
+}
+
+impl<'a, T: Ord, R: RangeBounds<T>> Iterator for SkipListRange<'a, T, R> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<&'a T> {
+    let node = self.current.take()?;
+
+    let is_in_range = match self.range.end_bound() {
+      Bound::Included(end) => node.borrow().value <= *end,
+      Bound::Excluded(end) => node.borrow().value < *end,
+      Bound::Unbounded => true,
+    };
+
+    if !is_in_range {
+      return None;
+    }
+
+    self.current = node.borrow().forward[0].as_ref().map(Rc::clone);
+
+    Some(unsafe { SkipNode::value(&node) })
+  }
+}
+
+impl<T: Ord, R: RangeBounds<T>> FusedIterator for SkipListRange<'_, T, R> {}
+
+#[cfg(test)]
+mod tests {
+  use std::thread;
+
+  use super::*;
+
+  #[test]
+  fn shared_linked_list_keeps_every_concurrent_insert() {
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 500;
+
+    let list = Arc::new(SharedLinkedList::new());
+
+    let handles = (0..THREADS).map(|thread_index| {
+      let list = Arc::clone(&list);
+
+      thread::spawn(move || {
+        for index in 0..PER_THREAD {
+          let value = thread_index * PER_THREAD + index;
+
+          if thread_index % 2 == 0 {
+            list.add_last(value);
+          } else {
+            list.add_first(value);
+          }
+        }
+      })
+    }).collect::<Vec<_>>();
+
+    for handle in handles {
+      handle.join().unwrap();
+    }
+
+    assert_eq!(list.size(), THREADS * PER_THREAD);
+
+    let contents = list.to_vec();
+
+    for thread_index in 0..THREADS {
+      let values = contents.iter().copied().filter(|value| value / PER_THREAD == thread_index).collect::<Vec<_>>();
+      let mut expected = (thread_index * PER_THREAD..(thread_index + 1) * PER_THREAD).collect::<Vec<_>>();
+
+      if thread_index % 2 == 1 {
+        expected.reverse();
+      }
+
+      assert_eq!(values, expected);
+    }
+  }
+
+  #[test]
+  fn shared_linked_list_hands_each_value_to_one_remover() {
+    const PRODUCERS: usize = 4;
+    const PER_PRODUCER: usize = 1000;
+
+    let list = Arc::new(SharedLinkedList::new());
+
+    let producers = (0..PRODUCERS).map(|producer| {
+      let list = Arc::clone(&list);
+
+      thread::spawn(move || {
+        for index in 0..PER_PRODUCER {
+          list.add_last(producer * PER_PRODUCER + index);
+        }
+      })
+    }).collect::<Vec<_>>();
+
+    let consumers = (0..PRODUCERS).map(|consumer| {
+      let list = Arc::clone(&list);
+
+      thread::spawn(move || {
+        let mut removed = Vec::new();
+
+        while removed.len() < PER_PRODUCER / 2 {
+          let value = if consumer % 2 == 0 { list.remove_first() } else { list.remove_last() };
+
+          match value {
+            Ok(value) => removed.push(value),
+            Err(_) => thread::yield_now(),
+          }
+        }
+
+        removed
+      })
+    }).collect::<Vec<_>>();
+
+    for producer in producers {
+      producer.join().unwrap();
+    }
+
+    let mut values = consumers.into_iter().flat_map(|consumer| consumer.join().unwrap()).collect::<Vec<_>>();
+
+    assert_eq!(list.size(), PRODUCERS * PER_PRODUCER / 2);
+    assert_eq!(list.to_vec().len(), list.size());
+
+    values.extend(list.to_vec());
+    values.sort_unstable();
+
+    assert_eq!(values, (0..PRODUCERS * PER_PRODUCER).collect::<Vec<_>>());
+
+    list.clear();
+
+    assert!(list.is_empty());
+    assert_eq!(list.peek_front(), None);
+  }
+
+  fn list(values: &[i32]) -> LinkedList<i32> {
+    values.iter().copied().collect()
+  }
+
+  #[test]
+  fn middle_picks_single_or_pair() {
+    assert!(list(&[]).middle().is_none());
+    assert!(matches!(list(&[7]).middle(), Some(Middle::Single(&7))));
+    assert!(matches!(list(&[1, 2]).middle(), Some(Middle::Pair(&1, &2))));
+    assert!(matches!(list(&[1, 2, 3, 4, 5]).middle(), Some(Middle::Single(&3))));
+    assert!(matches!(list(&[1, 2, 3, 4, 5, 6]).middle(), Some(Middle::Pair(&3, &4))));
+  }
+
+  #[test]
+  fn has_loop_detects_cycles() {
+    assert!(!list(&[]).has_loop());
+    assert!(!list(&[1]).has_loop());
+    assert!(!list(&[1, 2, 3, 4]).has_loop());
+
+    let looped = list(&[1, 2, 3, 4, 5]);
+    let head = looped.head.as_ref().map(Rc::clone);
+    let tail = looped.tail.as_ref().map(Rc::clone).unwrap();
+
+    tail.borrow_mut().next = head;
+    assert!(looped.has_loop());
+
+    tail.borrow_mut().next = None;
+    assert!(!looped.has_loop());
+  }
+
+  #[test]
+  fn rotate_wraps_around_the_length() {
+    let mut empty = list(&[]);
+    empty.rotate_left(3);
+    empty.rotate_right(3);
+    assert!(empty.is_empty());
+
+    let mut single = list(&[1]);
+    single.rotate_left(5);
+    single.rotate_right(5);
+    assert_eq!(single.to_vec(), vec![1]);
+
+    let mut values = list(&[1, 2, 3, 4, 5]);
+
+    values.rotate_left(0);
+    assert_eq!(values.to_vec(), vec![1, 2, 3, 4, 5]);
+
+    values.rotate_left(2);
+    assert_eq!(values.to_vec(), vec![3, 4, 5, 1, 2]);
+
+    values.rotate_right(2);
+    assert_eq!(values.to_vec(), vec![1, 2, 3, 4, 5]);
+
+    values.rotate_left(5);
+    values.rotate_right(0);
+    assert_eq!(values.to_vec(), vec![1, 2, 3, 4, 5]);
+
+    values.rotate_right(7);
+    assert_eq!(values.to_vec(), vec![4, 5, 1, 2, 3]);
+
+    values.rotate_left(12);
+    assert_eq!(values.to_vec(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(values.peek_back(), Some(&5));
+    assert_eq!(values.size(), 5);
+  }
+
+  #[test]
+  fn reverse_in_groups_reverses_each_chunk() {
+    let mut empty = list(&[]);
+    empty.reverse_in_groups(3);
+    assert!(empty.is_empty());
+
+    let mut single = list(&[1]);
+    single.reverse_in_groups(3);
+    assert_eq!(single.to_vec(), vec![1]);
+
+    let mut values = list(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+    values.reverse_in_groups(0);
+    values.reverse_in_groups(1);
+    assert_eq!(values.to_vec(), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+    values.reverse_in_groups(3);
+    assert_eq!(values.to_vec(), vec![3, 2, 1, 6, 5, 4, 8, 7]);
+
+    values.reverse_in_groups(8);
+    assert_eq!(values.to_vec(), vec![7, 8, 4, 5, 6, 1, 2, 3]);
+
+    values.reverse_in_groups(20);
+    assert_eq!(values.to_vec(), vec![3, 2, 1, 6, 5, 4, 8, 7]);
+    assert_eq!(values.peek_back(), Some(&7));
+    assert_eq!(values.size(), 8);
+  }
+
+  #[test]
+  fn is_palindrome_compares_both_ends() {
+    assert!(list(&[]).is_palindrome());
+    assert!(list(&[1]).is_palindrome());
+    assert!(list(&[1, 1]).is_palindrome());
+    assert!(!list(&[1, 2]).is_palindrome());
+    assert!(list(&[1, 2, 3, 2, 1]).is_palindrome());
+    assert!(list(&[1, 2, 2, 1]).is_palindrome());
+    assert!(!list(&[1, 2, 3, 1]).is_palindrome());
+  }
+
+  #[test]
+  fn remove_duplicates_keeps_first_occurrences() {
+    let mut empty = list(&[]);
+    empty.remove_duplicates();
+    assert!(empty.is_empty());
+
+    let mut single = list(&[1]);
+    single.remove_duplicates();
+    assert_eq!(single.to_vec(), vec![1]);
+
+    let mut values = list(&[3, 1, 3, 2, 1, 1, 4, 3]);
+    values.remove_duplicates();
+    assert_eq!(values.to_vec(), vec![3, 1, 2, 4]);
+    assert_eq!(values.peek_back(), Some(&4));
+    assert_eq!(values.size(), 4);
+
+    let mut same = list(&[5, 5, 5]);
+    same.remove_duplicates();
+    assert_eq!(same.to_vec(), vec![5]);
+  }
+
+  #[test]
+  fn partition_around_keeps_relative_order() {
+    let mut empty = list(&[]);
+    empty.partition_around(&0);
+    assert!(empty.is_empty());
+
+    let mut single = list(&[1]);
+    single.partition_around(&5);
+    assert_eq!(single.to_vec(), vec![1]);
+
+    let mut values = list(&[7, 3, 5, 9, 1, 5, 8, 2]);
+    values.partition_around(&5);
+    assert_eq!(values.to_vec(), vec![3, 1, 2, 5, 5, 7, 9, 8]);
+    assert_eq!(values.peek_back(), Some(&8));
+
+    values.partition_around(&100);
+    assert_eq!(values.to_vec(), vec![3, 1, 2, 5, 5, 7, 9, 8]);
+
+    values.partition_around(&0);
+    assert_eq!(values.to_vec(), vec![3, 1, 2, 5, 5, 7, 9, 8]);
+    assert_eq!(values.size(), 8);
   }
 }
@@ -1,75 +1,184 @@
-use std::fmt::{Display, Formatter};
+use std::alloc::{handle_alloc_error, Layout};
+use std::cell::UnsafeCell;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::{Chain, FusedIterator};
+use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+use std::ops::{Index, IndexMut};
+use std::ptr::{self, NonNull};
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
-use crate::heaps::Heap;
+use crate::allocators::{Allocator, Global};
+use crate::errors::Error;
+use crate::heaps::{Comparator, Heap, MaxFirst, MinFirst};
 
 pub trait Queue<T> {
-  fn enqueue(&mut self, value: T) -> Result<(), &'static str>;
-  fn dequeue(&mut self) -> Result<T, &'static str>;
-  fn peek(&self) -> Result<&T, &'static str>;
+  fn enqueue(&mut self, value: T) -> Result<(), Error>;
+  fn dequeue(&mut self) -> Result<T, Error>;
+  fn peek(&self) -> Result<&T, Error>;
   fn is_empty(&self) -> bool;
   fn is_full(&self) -> bool {
     false
   }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowMode {
+  #[default]
+  Reject,
+  Overwrite,
+}
+
 pub struct ArrayQueue<T, const SIZE: usize> {
-  items: [Option<T>; SIZE],
+  items: [MaybeUninit<T>; SIZE],
   length: usize,
   front: usize,
-  back: usize
+  mode: OverflowMode,
 }
 
 impl <T, const SIZE: usize> ArrayQueue<T, SIZE> {
-  const INIT: Option<T> = None;
-
   pub fn new() -> Self {
+    Self::with_mode(OverflowMode::Reject)
+  }
+
+  pub fn with_mode(mode: OverflowMode) -> Self {
     Self {
-      items: [ArrayQueue::<T, SIZE>::INIT; SIZE],
+      items: [const { MaybeUninit::uninit() }; SIZE],
       length: 0,
       front: 0,
-      back: 0
+      mode,
     }
   }
-}
 
-impl<T, const SIZE: usize> Queue<T> for ArrayQueue<T, SIZE> {
-  fn enqueue(&mut self, value: T) -> Result<(), &'static str> {
-    if self.is_full() {
-      return Err("Queue is full");
+  pub fn mode(&self) -> OverflowMode {
+    self.mode
+  }
+
+  pub fn push(&mut self, value: T) -> Result<Option<T>, Error> {
+    if self.is_full() && self.mode == OverflowMode::Reject {
+      return Err(Error::CapacityExceeded { capacity: SIZE });
+    }
+
+    Ok(self.enqueue_overwriting(value))
+  }
+
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn capacity(&self) -> usize {
+    SIZE
+  }
+
+  pub fn clear(&mut self) {
+    let (front, back) = self.as_mut_slices();
+    let (front, back) = (front as *mut [T], back as *mut [T]);
+
+    self.front = 0;
+    self.length = 0;
+
+    unsafe {
+      ptr::drop_in_place(front);
+      ptr::drop_in_place(back);
+    }
+  }
+
+  pub fn drain(&mut self) -> Drain<'_, T, SIZE> {
+    Drain { queue: self }
+  }
+
+  pub fn as_slices(&self) -> (&[T], &[T]) {
+    let (front, back) = self.slice_lengths();
+    let items = self.items.as_ptr() as *const T;
+
+    unsafe {
+      (
+        slice::from_raw_parts(items.add(self.front), front),
+        slice::from_raw_parts(items, back),
+      )
+    }
+  }
+
+  pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+    let (front, back) = self.slice_lengths();
+    let items = self.items.as_mut_ptr() as *mut T;
+
+    unsafe {
+      (
+        slice::from_raw_parts_mut(items.add(self.front), front),
+        slice::from_raw_parts_mut(items, back),
+      )
     }
+  }
+
+  pub fn iter(&self) -> RingIter<'_, T> {
+    let (front, back) = self.as_slices();
+
+    front.iter().chain(back)
+  }
+
+  pub fn iter_mut(&mut self) -> RingIterMut<'_, T> {
+    let (front, back) = self.as_mut_slices();
+
+    front.iter_mut().chain(back)
+  }
 
-    if !self.is_empty() {
-      self.back = (self.back + 1) % SIZE;
+  fn slice_lengths(&self) -> (usize, usize) {
+    let front = self.length.min(SIZE - self.front);
+
+    (front, self.length - front)
+  }
+
+  fn physical_index(&self, index: usize) -> usize {
+    (self.front + index) % SIZE
+  }
+
+  fn enqueue_overwriting(&mut self, value: T) -> Option<T> {
+    if SIZE == 0 {
+      return Some(value);
     }
-    
-    self.items[self.back] = Some(value);
+
+    let evicted = if self.is_full() { self.dequeue().ok() } else { None };
+
+    self.items[self.physical_index(self.length)].write(value);
     self.length += 1;
 
-    Ok(())
+    evicted
+  }
+}
+
+impl<T, const SIZE: usize> Queue<T> for ArrayQueue<T, SIZE> {
+  fn enqueue(&mut self, value: T) -> Result<(), Error> {
+    self.push(value).map(drop)
   }
 
-  fn dequeue(&mut self) -> Result<T, &'static str> {
+  fn dequeue(&mut self) -> Result<T, Error> {
     if self.is_empty() {
-      return Err("Queue is empty");
+      return Err(Error::Empty);
     }
 
-    let value = self.items[self.front].take().unwrap();
+    let value = unsafe { self.items[self.front].assume_init_read() };
 
-    if self.length != 1 {
-      self.front = (self.front + 1) % SIZE;
-    }
-    
+    self.front = self.physical_index(1);
     self.length -= 1;
-    
+
     Ok(value)
   }
 
-  fn peek(&self) -> Result<&T, &'static str> {
+  fn peek(&self) -> Result<&T, Error> {
     if self.is_empty() {
-      return Err("Queue is empty");
+      return Err(Error::Empty);
     }
 
-    Ok(self.items[self.front].as_ref().unwrap())
+    Ok(unsafe { self.items[self.front].assume_init_ref() })
   }
 
   fn is_empty(&self) -> bool {
@@ -81,107 +190,423 @@ impl<T, const SIZE: usize> Queue<T> for ArrayQueue<T, SIZE> {
   }
 }
 
+impl<T, const SIZE: usize> Default for ArrayQueue<T, SIZE> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, const SIZE: usize> Drop for ArrayQueue<T, SIZE> {
+  fn drop(&mut self) {
+    self.clear();
+  }
+}
+
+impl<T: Debug, const SIZE: usize> Debug for ArrayQueue<T, SIZE> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
 impl<T: Display, const SIZE: usize> Display for ArrayQueue<T, SIZE> {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     let mut formatted_items = String::new();
 
-    let mut current_index = self.front;
-    let mut seen_count: usize = 0;
+    for (index, item) in self.iter().enumerate() {
+      let delimeter = if index != self.length - 1 { " <- " } else { "" };
 
-    while seen_count < self.length {
-      seen_count += 1;
+      formatted_items.push_str(&(item.to_string() + delimeter));
+    }
 
-      let delimeter = if seen_count != self.length { " <- " } else { "" };
+    write!(f, "[{}]", formatted_items)?;
 
-      formatted_items.push_str(&(self.items[current_index].as_ref().unwrap().to_string() + delimeter));
+    Ok(())
+  }
+}
 
-      current_index += 1;
+impl<'a, T, const SIZE: usize> IntoIterator for &'a ArrayQueue<T, SIZE> {
+  type Item = &'a T;
+  type IntoIter = RingIter<'a, T>;
 
-      if current_index == SIZE {
-        current_index = 0;
-      }
-    }
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
 
-    write!(f, "[{}]", formatted_items)?;
+pub struct Drain<'a, T, const SIZE: usize> {
+  queue: &'a mut ArrayQueue<T, SIZE>,
+}
 
-    Ok(())
+impl<T, const SIZE: usize> Iterator for Drain<'_, T, SIZE> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.queue.dequeue().ok()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.queue.length, Some(self.queue.length))
   }
 }
 
-pub struct PriorityQueue {
-  items: Vec<u32>,
+impl<T, const SIZE: usize> ExactSizeIterator for Drain<'_, T, SIZE> {}
+
+impl<T, const SIZE: usize> FusedIterator for Drain<'_, T, SIZE> {}
+
+impl<T, const SIZE: usize> Drop for Drain<'_, T, SIZE> {
+  fn drop(&mut self) {
+    self.queue.clear();
+  }
+}
+
+const DEQUE_MIN_CAPACITY: usize = 4;
+
+pub struct Deque<T> {
+  items: NonNull<T>,
+  capacity: usize,
+  head: usize,
+  length: usize,
+  marker: PhantomData<T>,
 }
 
-impl PriorityQueue {
+unsafe impl<T: Send> Send for Deque<T> {}
+unsafe impl<T: Sync> Sync for Deque<T> {}
+
+pub type RingIter<'a, T> = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+pub type RingIterMut<'a, T> = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+impl<T> Deque<T> {
+  const IS_ZERO_SIZED: bool = mem::size_of::<T>() == 0;
+
   pub fn new() -> Self {
+    Self::with_capacity(0)
+  }
+
+  pub fn with_capacity(capacity: usize) -> Self {
+    let capacity = if Self::IS_ZERO_SIZED { usize::MAX } else { capacity };
+
     Self {
-      items: Vec::new()
+      items: Self::allocate(capacity),
+      capacity,
+      head: 0,
+      length: 0,
+      marker: PhantomData,
     }
   }
 
-  fn shift_items_to_insert(&mut self, value: u32) -> usize {
-    self.items.push(*self.items.last().unwrap());
+  pub fn push_back(&mut self, value: T) {
+    self.grow_if_full();
 
-    for index in (0..self.items.len()).rev() {
-      if index == 0 || self.items[index - 1] < value {
-        return index;
-      }
+    unsafe {
+      self.slot(self.length).write(value);
+    }
+
+    self.length += 1;
+  }
 
-      self.items[index] = self.items[index - 1];
+  pub fn push_front(&mut self, value: T) {
+    self.grow_if_full();
+
+    self.head = self.physical_index(self.capacity - 1);
+
+    unsafe {
+      self.slot(0).write(value);
     }
 
-    0
+    self.length += 1;
   }
-}
 
-impl Queue<u32> for PriorityQueue {
-  fn enqueue(&mut self, value: u32) -> Result<(), &'static str> {
-    if self.is_full() {
-      return Err("Queue is full");
+  pub fn pop_front(&mut self) -> Result<T, Error> {
+    if self.is_empty() {
+      return Err(Error::Empty);
     }
 
+    let value = unsafe { self.slot(0).read() };
+
+    self.head = self.physical_index(1);
+    self.length -= 1;
+
+    Ok(value)
+  }
+
+  pub fn pop_back(&mut self) -> Result<T, Error> {
     if self.is_empty() {
-      self.items.push(value);
+      return Err(Error::Empty);
+    }
 
-      return Ok(());
+    self.length -= 1;
+
+    Ok(unsafe { self.slot(self.length).read() })
+  }
+
+  pub fn peek_front(&self) -> Result<&T, Error> {
+    self.get(0).ok_or(Error::Empty)
+  }
+
+  pub fn peek_back(&self) -> Result<&T, Error> {
+    self.length.checked_sub(1).and_then(|index| self.get(index)).ok_or(Error::Empty)
+  }
+
+  pub fn get(&self, index: usize) -> Option<&T> {
+    if index >= self.length {
+      return None;
     }
 
-    let index = self.shift_items_to_insert(value);
-    self.items[index] = value;
+    Some(unsafe { &*self.slot(index) })
+  }
 
-    Ok(())
+  pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+    if index >= self.length {
+      return None;
+    }
+
+    Some(unsafe { &mut *self.slot(index) })
   }
 
-  fn dequeue(&mut self) -> Result<u32, &'static str> {
-    if self.is_empty() {
-      return Err("Queue is empty");
+  pub fn len(&self) -> usize {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  pub fn clear(&mut self) {
+    let (front, back) = self.as_mut_slices();
+    let (front, back) = (front as *mut [T], back as *mut [T]);
+
+    self.head = 0;
+    self.length = 0;
+
+    unsafe {
+      ptr::drop_in_place(front);
+      ptr::drop_in_place(back);
+    }
+  }
+
+  pub fn as_slices(&self) -> (&[T], &[T]) {
+    let (front, back) = self.slice_lengths();
+
+    unsafe {
+      (
+        slice::from_raw_parts(self.items.as_ptr().add(self.head), front),
+        slice::from_raw_parts(self.items.as_ptr(), back),
+      )
     }
+  }
 
-    Ok(self.items.pop().unwrap())
+  pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+    let (front, back) = self.slice_lengths();
+
+    unsafe {
+      (
+        slice::from_raw_parts_mut(self.items.as_ptr().add(self.head), front),
+        slice::from_raw_parts_mut(self.items.as_ptr(), back),
+      )
+    }
   }
 
-  fn peek(&self) -> Result<&u32, &'static str> {
-    if self.is_empty() {
-      return Err("Queue is empty");
+  pub fn make_contiguous(&mut self) -> &mut [T] {
+    if self.length > self.capacity - self.head {
+      unsafe {
+        slice::from_raw_parts_mut(self.items.as_ptr() as *mut MaybeUninit<T>, self.capacity).rotate_left(self.head);
+      }
+
+      self.head = 0;
     }
 
-    Ok(self.items.first().unwrap())
+    self.as_mut_slices().0
   }
 
-  fn is_empty(&self) -> bool {
-    self.items.len() == 0
+  pub fn rotate_left(&mut self, k: usize) {
+    if self.length == 0 {
+      return;
+    }
+
+    let k = k % self.length;
+
+    if k <= self.length - k {
+      for _ in 0..k {
+        let value = self.pop_front().unwrap();
+
+        self.push_back(value);
+      }
+    } else {
+      self.rotate_right(self.length - k);
+    }
+  }
+
+  pub fn rotate_right(&mut self, k: usize) {
+    if self.length == 0 {
+      return;
+    }
+
+    let k = k % self.length;
+
+    if k <= self.length - k {
+      for _ in 0..k {
+        let value = self.pop_back().unwrap();
+
+        self.push_front(value);
+      }
+    } else {
+      self.rotate_left(self.length - k);
+    }
+  }
+
+  pub fn iter(&self) -> RingIter<'_, T> {
+    let (front, back) = self.as_slices();
+
+    front.iter().chain(back)
+  }
+
+  pub fn iter_mut(&mut self) -> RingIterMut<'_, T> {
+    let (front, back) = self.as_mut_slices();
+
+    front.iter_mut().chain(back)
+  }
+
+  fn slice_lengths(&self) -> (usize, usize) {
+    let front = self.length.min(self.capacity - self.head);
+
+    (front, self.length - front)
+  }
+
+  fn physical_index(&self, index: usize) -> usize {
+    let room = self.capacity - self.head;
+
+    if index < room { self.head + index } else { index - room }
+  }
+
+  unsafe fn slot(&self, index: usize) -> *mut T {
+    self.items.as_ptr().add(self.physical_index(index))
+  }
+
+  fn grow_if_full(&mut self) {
+    if self.length < self.capacity {
+      return;
+    }
+
+    let capacity = self.capacity.checked_mul(2).expect("Deque capacity overflow").max(DEQUE_MIN_CAPACITY);
+    let items = Self::allocate(capacity);
+    let (front, back) = self.slice_lengths();
+
+    unsafe {
+      ptr::copy_nonoverlapping(self.items.as_ptr().add(self.head), items.as_ptr(), front);
+      ptr::copy_nonoverlapping(self.items.as_ptr(), items.as_ptr().add(front), back);
+
+      Self::deallocate(self.items, self.capacity);
+    }
+
+    self.items = items;
+    self.capacity = capacity;
+    self.head = 0;
+  }
+
+  fn allocate(capacity: usize) -> NonNull<T> {
+    if Self::IS_ZERO_SIZED || capacity == 0 {
+      return NonNull::dangling();
+    }
+
+    let layout = Layout::array::<T>(capacity).expect("Deque capacity overflow");
+
+    match Global.allocate(layout) {
+      Some(items) => items.cast(),
+      None => handle_alloc_error(layout),
+    }
+  }
+
+  unsafe fn deallocate(items: NonNull<T>, capacity: usize) {
+    if Self::IS_ZERO_SIZED || capacity == 0 {
+      return;
+    }
+
+    Global.deallocate(items.cast(), Layout::array::<T>(capacity).unwrap());
   }
 }
 
-impl Display for PriorityQueue {
-  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-    let mut formatted_items = String::new();
+impl<T> Queue<T> for Deque<T> {
+  fn enqueue(&mut self, value: T) -> Result<(), Error> {
+    self.push_back(value);
+
+    Ok(())
+  }
 
-    for (index, item) in self.items.iter().enumerate() {
-      let delimeter = if index != self.items.len() - 1 { " <- " } else { "" };
+  fn dequeue(&mut self) -> Result<T, Error> {
+    self.pop_front()
+  }
 
-      formatted_items.push_str(&(item.to_string() + delimeter));
+  fn peek(&self) -> Result<&T, Error> {
+    self.peek_front()
+  }
+
+  fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+}
+
+impl<T> Drop for Deque<T> {
+  fn drop(&mut self) {
+    self.clear();
+
+    unsafe {
+      Self::deallocate(self.items, self.capacity);
     }
+  }
+}
+
+impl<T> Default for Deque<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> Index<usize> for Deque<T> {
+  type Output = T;
+
+  fn index(&self, index: usize) -> &T {
+    let length = self.length;
+
+    self.get(index).unwrap_or_else(|| panic!("Index {} is out of bounds for length {}", index, length))
+  }
+}
+
+impl<T> IndexMut<usize> for Deque<T> {
+  fn index_mut(&mut self, index: usize) -> &mut T {
+    let length = self.length;
+
+    self.get_mut(index).unwrap_or_else(|| panic!("Index {} is out of bounds for length {}", index, length))
+  }
+}
+
+impl<T: Clone> Clone for Deque<T> {
+  fn clone(&self) -> Self {
+    self.iter().cloned().collect()
+  }
+}
+
+impl<T: PartialEq> PartialEq for Deque<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.length == other.length && self.iter().eq(other.iter())
+  }
+}
+
+impl<T: Eq> Eq for Deque<T> {}
+
+impl<T: Debug> Debug for Deque<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T: Display> Display for Deque<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let formatted_items = self.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(" <- ");
 
     write!(f, "[{}]", formatted_items)?;
 
@@ -189,24 +614,1109 @@ impl Display for PriorityQueue {
   }
 }
 
-pub struct HeapPriorityQueue {
-  heap: Heap<i32>,
+impl<T> Extend<T> for Deque<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for value in iter {
+      self.push_back(value);
+    }
+  }
 }
 
-impl HeapPriorityQueue {
-  pub fn new() -> Self {
-    Self { heap: Heap::new() }
+impl<T> FromIterator<T> for Deque<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut deque = Self::new();
+
+    deque.extend(iter);
+
+    deque
   }
+}
 
-  pub fn enqueue(&mut self, value: i32) {
-    self.heap.insert(value);
+impl<'a, T> IntoIterator for &'a Deque<T> {
+  type Item = &'a T;
+  type IntoIter = RingIter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
   }
+}
 
-  pub fn dequeue(&mut self) -> Result<i32, &'static str> {
-    self.heap.remove()
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+  type Item = &'a mut T;
+  type IntoIter = RingIterMut<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
   }
+}
 
-  pub fn is_empty(&self) -> bool {
-    self.heap.is_empty()
+pub struct PriorityQueue<T, C: Comparator<T> = MaxFirst> {
+  heap: Heap<T, C>,
+}
+
+impl<T: Ord> PriorityQueue<T> {
+  pub fn new() -> Self {
+    Self::with_comparator(MaxFirst)
   }
-}
\ No newline at end of file
+}
+
+impl<T, C: Comparator<T>> PriorityQueue<T, C> {
+  pub fn with_comparator(comparator: C) -> Self {
+    Self { heap: Heap::with_comparator(comparator) }
+  }
+
+  pub fn len(&self) -> usize {
+    self.heap.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.heap.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.heap.clear();
+  }
+
+  pub fn iter(&self) -> slice::Iter<'_, T> {
+    self.heap.iter()
+  }
+
+  pub fn into_sorted_vec(self) -> Vec<T> {
+    self.heap.into_sorted_vec()
+  }
+
+  fn sorted_refs(&self) -> Vec<&T> {
+    let mut items = self.heap.iter().collect::<Vec<_>>();
+
+    items.sort_by(|left, right| self.heap.comparator().compare(right, left));
+
+    items
+  }
+}
+
+impl<T, C: Comparator<T>> Queue<T> for PriorityQueue<T, C> {
+  fn enqueue(&mut self, value: T) -> Result<(), Error> {
+    self.heap.insert(value);
+
+    Ok(())
+  }
+
+  fn dequeue(&mut self) -> Result<T, Error> {
+    self.heap.remove()
+  }
+
+  fn peek(&self) -> Result<&T, Error> {
+    self.heap.peek()
+  }
+
+  fn is_empty(&self) -> bool {
+    self.heap.is_empty()
+  }
+}
+
+impl<T: Ord> Default for PriorityQueue<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for PriorityQueue<T, C> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    self.heap.extend(iter);
+  }
+}
+
+impl<T: Ord> FromIterator<T> for PriorityQueue<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self { heap: iter.into_iter().collect() }
+  }
+}
+
+impl<T: Debug, C: Comparator<T>> Debug for PriorityQueue<T, C> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.sorted_refs()).finish()
+  }
+}
+
+impl<T: Display, C: Comparator<T>> Display for PriorityQueue<T, C> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let mut formatted_items = String::new();
+    let items = self.sorted_refs();
+
+    for (index, item) in items.iter().enumerate() {
+      let delimeter = if index != items.len() - 1 { " <- " } else { "" };
+
+      formatted_items.push_str(&(item.to_string() + delimeter));
+    }
+
+    write!(f, "[{}]", formatted_items)?;
+
+    Ok(())
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+  slot: usize,
+  generation: u64,
+}
+
+struct IndexedSlot {
+  generation: u64,
+  position: Option<usize>,
+}
+
+struct IndexedEntry<T, P> {
+  value: T,
+  priority: P,
+  slot: usize,
+}
+
+struct EntryComparator<C>(C);
+
+impl<T, P, C: Comparator<P>> Comparator<IndexedEntry<T, P>> for EntryComparator<C> {
+  fn compare(&self, left: &IndexedEntry<T, P>, right: &IndexedEntry<T, P>) -> Ordering {
+    self.0.compare(&left.priority, &right.priority)
+  }
+}
+
+pub struct IndexedPriorityQueue<T, P, C: Comparator<P> = MaxFirst> {
+  heap: Heap<IndexedEntry<T, P>, EntryComparator<C>>,
+  slots: Vec<IndexedSlot>,
+  free_slots: Vec<usize>,
+}
+
+impl<T, P: Ord> IndexedPriorityQueue<T, P> {
+  pub fn new() -> Self {
+    Self::with_comparator(MaxFirst)
+  }
+}
+
+impl<T, P, C: Comparator<P>> IndexedPriorityQueue<T, P, C> {
+  pub fn with_comparator(comparator: C) -> Self {
+    Self {
+      heap: Heap::with_comparator(EntryComparator(comparator)),
+      slots: Vec::new(),
+      free_slots: Vec::new(),
+    }
+  }
+
+  pub fn enqueue(&mut self, value: T, priority: P) -> Handle {
+    let slot = match self.free_slots.pop() {
+      Some(slot) => slot,
+      None => {
+        self.slots.push(IndexedSlot { generation: 0, position: None });
+
+        self.slots.len() - 1
+      }
+    };
+
+    let slots = &mut self.slots;
+
+    self.heap.insert_tracked(IndexedEntry { value, priority, slot }, &mut |entry, position| {
+      slots[entry.slot].position = Some(position);
+    });
+
+    Handle { slot, generation: self.slots[slot].generation }
+  }
+
+  pub fn dequeue(&mut self) -> Result<(T, P), Error> {
+    if self.heap.is_empty() {
+      return Err(Error::Empty);
+    }
+
+    Ok(self.remove_at(0))
+  }
+
+  pub fn peek(&self) -> Result<(&T, &P), Error> {
+    self.heap.peek().map(|entry| (&entry.value, &entry.priority))
+  }
+
+  pub fn peek_handle(&self) -> Result<Handle, Error> {
+    self.heap.peek().map(|entry| Handle { slot: entry.slot, generation: self.slots[entry.slot].generation })
+  }
+
+  pub fn change_priority(&mut self, handle: Handle, priority: P) -> Result<P, Error> {
+    let position = self.position(handle)?;
+    let entry = self.heap.get_mut(position).unwrap();
+    let previous = mem::replace(&mut entry.priority, priority);
+    let slots = &mut self.slots;
+
+    self.heap.update_at_tracked(position, &mut |entry, position| {
+      slots[entry.slot].position = Some(position);
+    });
+
+    Ok(previous)
+  }
+
+  pub fn remove(&mut self, handle: Handle) -> Result<(T, P), Error> {
+    let position = self.position(handle)?;
+
+    Ok(self.remove_at(position))
+  }
+
+  pub fn contains(&self, handle: Handle) -> bool {
+    self.position(handle).is_ok()
+  }
+
+  pub fn get(&self, handle: Handle) -> Result<(&T, &P), Error> {
+    let entry = self.heap.get(self.position(handle)?).unwrap();
+
+    Ok((&entry.value, &entry.priority))
+  }
+
+  pub fn len(&self) -> usize {
+    self.heap.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.heap.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    while !self.heap.is_empty() {
+      self.remove_at(self.heap.len() - 1);
+    }
+  }
+
+  fn position(&self, handle: Handle) -> Result<usize, Error> {
+    match self.slots.get(handle.slot) {
+      Some(slot) if slot.generation == handle.generation => slot.position.ok_or(Error::InvalidHandle),
+      _ => Err(Error::InvalidHandle),
+    }
+  }
+
+  fn remove_at(&mut self, position: usize) -> (T, P) {
+    let slots = &mut self.slots;
+
+    let entry = self.heap.remove_at_tracked(position, &mut |entry, position| {
+      slots[entry.slot].position = Some(position);
+    });
+
+    let slot = &mut self.slots[entry.slot];
+
+    slot.position = None;
+    slot.generation += 1;
+    self.free_slots.push(entry.slot);
+
+    (entry.value, entry.priority)
+  }
+}
+
+impl<T, P: Ord> Default for IndexedPriorityQueue<T, P> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Debug, P: Debug, C: Comparator<P>> Debug for IndexedPriorityQueue<T, P, C> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.heap.iter().map(|entry| (&entry.value, &entry.priority))).finish()
+  }
+}
+
+pub struct EnqueueError<T> {
+  pub error: Error,
+  pub value: T,
+}
+
+impl<T> Debug for EnqueueError<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("EnqueueError").field("error", &self.error).finish_non_exhaustive()
+  }
+}
+
+impl<T> Display for EnqueueError<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.error)
+  }
+}
+
+impl<T> std::error::Error for EnqueueError<T> {}
+
+struct BlockingState<T, const SIZE: usize> {
+  queue: ArrayQueue<T, SIZE>,
+  is_closed: bool,
+}
+
+pub struct BlockingQueue<T, const SIZE: usize> {
+  state: Mutex<BlockingState<T, SIZE>>,
+  not_empty: Condvar,
+  not_full: Condvar,
+}
+
+impl<T, const SIZE: usize> BlockingQueue<T, SIZE> {
+  pub fn new() -> Self {
+    const { assert!(SIZE > 0, "Blocking queue capacity must be positive") };
+
+    Self {
+      state: Mutex::new(BlockingState { queue: ArrayQueue::new(), is_closed: false }),
+      not_empty: Condvar::new(),
+      not_full: Condvar::new(),
+    }
+  }
+
+  pub fn enqueue(&self, value: T) -> Result<(), EnqueueError<T>> {
+    let state = self.lock_state();
+    let state = self.not_full.wait_while(state, |state| state.queue.is_full() && !state.is_closed).unwrap_or_else(PoisonError::into_inner);
+
+    self.push(state, value)
+  }
+
+  pub fn enqueue_timeout(&self, value: T, timeout: Duration) -> Result<(), EnqueueError<T>> {
+    let state = self.lock_state();
+    let (state, _) = self.not_full.wait_timeout_while(state, timeout, |state| state.queue.is_full() && !state.is_closed).unwrap_or_else(PoisonError::into_inner);
+
+    if state.queue.is_full() && !state.is_closed {
+      return Err(EnqueueError { error: Error::Timeout, value });
+    }
+
+    self.push(state, value)
+  }
+
+  pub fn try_enqueue(&self, value: T) -> Result<(), EnqueueError<T>> {
+    self.push(self.lock_state(), value)
+  }
+
+  pub fn dequeue(&self) -> Result<T, Error> {
+    let state = self.lock_state();
+    let state = self.not_empty.wait_while(state, |state| state.queue.is_empty() && !state.is_closed).unwrap_or_else(PoisonError::into_inner);
+
+    self.pop(state)
+  }
+
+  pub fn dequeue_timeout(&self, timeout: Duration) -> Result<T, Error> {
+    let state = self.lock_state();
+    let (state, _) = self.not_empty.wait_timeout_while(state, timeout, |state| state.queue.is_empty() && !state.is_closed).unwrap_or_else(PoisonError::into_inner);
+
+    if state.queue.is_empty() && !state.is_closed {
+      return Err(Error::Timeout);
+    }
+
+    self.pop(state)
+  }
+
+  pub fn try_dequeue(&self) -> Result<T, Error> {
+    self.pop(self.lock_state())
+  }
+
+  pub fn close(&self) {
+    self.lock_state().is_closed = true;
+
+    self.not_empty.notify_all();
+    self.not_full.notify_all();
+  }
+
+  pub fn is_closed(&self) -> bool {
+    self.lock_state().is_closed
+  }
+
+  pub fn len(&self) -> usize {
+    self.lock_state().queue.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.lock_state().queue.is_empty()
+  }
+
+  pub fn is_full(&self) -> bool {
+    self.lock_state().queue.is_full()
+  }
+
+  pub fn capacity(&self) -> usize {
+    SIZE
+  }
+
+  fn push(&self, mut state: MutexGuard<'_, BlockingState<T, SIZE>>, value: T) -> Result<(), EnqueueError<T>> {
+    if state.is_closed {
+      return Err(EnqueueError { error: Error::Closed, value });
+    }
+
+    if state.queue.is_full() {
+      return Err(EnqueueError { error: Error::CapacityExceeded { capacity: SIZE }, value });
+    }
+
+    state.queue.enqueue_overwriting(value);
+    drop(state);
+
+    self.not_empty.notify_one();
+
+    Ok(())
+  }
+
+  fn pop(&self, mut state: MutexGuard<'_, BlockingState<T, SIZE>>) -> Result<T, Error> {
+    let value = match state.queue.dequeue() {
+      Ok(value) => value,
+      Err(_) if state.is_closed => return Err(Error::Closed),
+      Err(error) => return Err(error),
+    };
+
+    drop(state);
+
+    self.not_full.notify_one();
+
+    Ok(value)
+  }
+
+  fn lock_state(&self) -> MutexGuard<'_, BlockingState<T, SIZE>> {
+    self.state.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+impl<T, const SIZE: usize> Default for BlockingQueue<T, SIZE> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+struct Channel<T, const SIZE: usize> {
+  queue: BlockingQueue<T, SIZE>,
+  senders: AtomicUsize,
+  receivers: AtomicUsize,
+}
+
+pub struct Sender<T, const SIZE: usize> {
+  channel: Arc<Channel<T, SIZE>>,
+}
+
+pub struct Receiver<T, const SIZE: usize> {
+  channel: Arc<Channel<T, SIZE>>,
+}
+
+pub fn bounded<T, const SIZE: usize>() -> (Sender<T, SIZE>, Receiver<T, SIZE>) {
+  let channel = Arc::new(Channel {
+    queue: BlockingQueue::new(),
+    senders: AtomicUsize::new(1),
+    receivers: AtomicUsize::new(1),
+  });
+
+  (Sender { channel: Arc::clone(&channel) }, Receiver { channel })
+}
+
+impl<T, const SIZE: usize> Sender<T, SIZE> {
+  pub fn enqueue(&self, value: T) -> Result<(), EnqueueError<T>> {
+    self.channel.queue.enqueue(value)
+  }
+
+  pub fn enqueue_timeout(&self, value: T, timeout: Duration) -> Result<(), EnqueueError<T>> {
+    self.channel.queue.enqueue_timeout(value, timeout)
+  }
+
+  pub fn try_enqueue(&self, value: T) -> Result<(), EnqueueError<T>> {
+    self.channel.queue.try_enqueue(value)
+  }
+
+  pub fn close(&self) {
+    self.channel.queue.close();
+  }
+
+  pub fn is_closed(&self) -> bool {
+    self.channel.queue.is_closed()
+  }
+}
+
+impl<T, const SIZE: usize> Clone for Sender<T, SIZE> {
+  fn clone(&self) -> Self {
+    self.channel.senders.fetch_add(1, AtomicOrdering::Relaxed);
+
+    Self { channel: Arc::clone(&self.channel) }
+  }
+}
+
+impl<T, const SIZE: usize> Drop for Sender<T, SIZE> {
+  fn drop(&mut self) {
+    if self.channel.senders.fetch_sub(1, AtomicOrdering::AcqRel) == 1 {
+      self.channel.queue.close();
+    }
+  }
+}
+
+impl<T, const SIZE: usize> Receiver<T, SIZE> {
+  pub fn dequeue(&self) -> Result<T, Error> {
+    self.channel.queue.dequeue()
+  }
+
+  pub fn dequeue_timeout(&self, timeout: Duration) -> Result<T, Error> {
+    self.channel.queue.dequeue_timeout(timeout)
+  }
+
+  pub fn try_dequeue(&self) -> Result<T, Error> {
+    self.channel.queue.try_dequeue()
+  }
+
+  pub fn close(&self) {
+    self.channel.queue.close();
+  }
+
+  pub fn is_closed(&self) -> bool {
+    self.channel.queue.is_closed()
+  }
+
+  pub fn iter(&self) -> ReceiverIter<'_, T, SIZE> {
+    ReceiverIter { receiver: self }
+  }
+}
+
+impl<T, const SIZE: usize> Clone for Receiver<T, SIZE> {
+  fn clone(&self) -> Self {
+    self.channel.receivers.fetch_add(1, AtomicOrdering::Relaxed);
+
+    Self { channel: Arc::clone(&self.channel) }
+  }
+}
+
+impl<T, const SIZE: usize> Drop for Receiver<T, SIZE> {
+  fn drop(&mut self) {
+    if self.channel.receivers.fetch_sub(1, AtomicOrdering::AcqRel) == 1 {
+      self.channel.queue.close();
+    }
+  }
+}
+
+pub struct ReceiverIter<'a, T, const SIZE: usize> {
+  receiver: &'a Receiver<T, SIZE>,
+}
+
+impl<T, const SIZE: usize> Iterator for ReceiverIter<'_, T, SIZE> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.receiver.dequeue().ok()
+  }
+}
+
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+struct SpscBuffer<T, const SIZE: usize> {
+  items: [UnsafeCell<MaybeUninit<T>>; SIZE],
+  head: CachePadded<AtomicUsize>,
+  tail: CachePadded<AtomicUsize>,
+}
+
+unsafe impl<T: Send, const SIZE: usize> Sync for SpscBuffer<T, SIZE> {}
+
+impl<T, const SIZE: usize> SpscBuffer<T, SIZE> {
+  const WRAP: usize = SIZE * 2;
+
+  fn advance(counter: usize, count: usize) -> usize {
+    (counter + count) % Self::WRAP
+  }
+
+  fn distance(head: usize, tail: usize) -> usize {
+    (tail + Self::WRAP - head) % Self::WRAP
+  }
+
+  fn slot(&self, counter: usize) -> *mut MaybeUninit<T> {
+    self.items[counter % SIZE].get()
+  }
+
+  fn len(&self) -> usize {
+    Self::distance(self.head.0.load(AtomicOrdering::Acquire), self.tail.0.load(AtomicOrdering::Acquire))
+  }
+}
+
+impl<T, const SIZE: usize> Drop for SpscBuffer<T, SIZE> {
+  fn drop(&mut self) {
+    let mut head = *self.head.0.get_mut();
+    let tail = *self.tail.0.get_mut();
+
+    while head != tail {
+      unsafe {
+        (*self.slot(head)).assume_init_drop();
+      }
+
+      head = Self::advance(head, 1);
+    }
+  }
+}
+
+pub struct Producer<T, const SIZE: usize> {
+  buffer: Arc<SpscBuffer<T, SIZE>>,
+}
+
+pub struct Consumer<T, const SIZE: usize> {
+  buffer: Arc<SpscBuffer<T, SIZE>>,
+}
+
+pub fn spsc<T, const SIZE: usize>() -> (Producer<T, SIZE>, Consumer<T, SIZE>) {
+  const { assert!(SIZE > 0, "Ring buffer capacity must be positive") };
+
+  let buffer = Arc::new(SpscBuffer {
+    items: [const { UnsafeCell::new(MaybeUninit::uninit()) }; SIZE],
+    head: CachePadded(AtomicUsize::new(0)),
+    tail: CachePadded(AtomicUsize::new(0)),
+  });
+
+  (Producer { buffer: Arc::clone(&buffer) }, Consumer { buffer })
+}
+
+impl<T, const SIZE: usize> Producer<T, SIZE> {
+  pub fn push(&mut self, value: T) -> Result<(), EnqueueError<T>> {
+    let tail = self.buffer.tail.0.load(AtomicOrdering::Relaxed);
+    let head = self.buffer.head.0.load(AtomicOrdering::Acquire);
+
+    if SpscBuffer::<T, SIZE>::distance(head, tail) == SIZE {
+      return Err(EnqueueError { error: Error::CapacityExceeded { capacity: SIZE }, value });
+    }
+
+    unsafe {
+      (*self.buffer.slot(tail)).write(value);
+    }
+
+    self.buffer.tail.0.store(SpscBuffer::<T, SIZE>::advance(tail, 1), AtomicOrdering::Release);
+
+    Ok(())
+  }
+
+  pub fn free_len(&self) -> usize {
+    SIZE - self.buffer.len()
+  }
+
+  pub fn is_full(&self) -> bool {
+    self.buffer.len() == SIZE
+  }
+
+  pub fn is_abandoned(&self) -> bool {
+    Arc::strong_count(&self.buffer) == 1
+  }
+
+  pub fn capacity(&self) -> usize {
+    SIZE
+  }
+}
+
+impl<T: Copy, const SIZE: usize> Producer<T, SIZE> {
+  pub fn push_slice(&mut self, values: &[T]) -> usize {
+    let tail = self.buffer.tail.0.load(AtomicOrdering::Relaxed);
+    let head = self.buffer.head.0.load(AtomicOrdering::Acquire);
+    let count = values.len().min(SIZE - SpscBuffer::<T, SIZE>::distance(head, tail));
+
+    for (offset, value) in values[..count].iter().enumerate() {
+      unsafe {
+        (*self.buffer.slot(SpscBuffer::<T, SIZE>::advance(tail, offset))).write(*value);
+      }
+    }
+
+    self.buffer.tail.0.store(SpscBuffer::<T, SIZE>::advance(tail, count), AtomicOrdering::Release);
+
+    count
+  }
+}
+
+impl<T, const SIZE: usize> Consumer<T, SIZE> {
+  pub fn pop(&mut self) -> Result<T, Error> {
+    let head = self.buffer.head.0.load(AtomicOrdering::Relaxed);
+    let tail = self.buffer.tail.0.load(AtomicOrdering::Acquire);
+
+    if head == tail {
+      return Err(Error::Empty);
+    }
+
+    let value = unsafe { (*self.buffer.slot(head)).assume_init_read() };
+
+    self.buffer.head.0.store(SpscBuffer::<T, SIZE>::advance(head, 1), AtomicOrdering::Release);
+
+    Ok(value)
+  }
+
+  pub fn peek(&self) -> Result<&T, Error> {
+    let head = self.buffer.head.0.load(AtomicOrdering::Relaxed);
+    let tail = self.buffer.tail.0.load(AtomicOrdering::Acquire);
+
+    if head == tail {
+      return Err(Error::Empty);
+    }
+
+    Ok(unsafe { (*self.buffer.slot(head)).assume_init_ref() })
+  }
+
+  pub fn len(&self) -> usize {
+    self.buffer.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.buffer.len() == 0
+  }
+
+  pub fn is_abandoned(&self) -> bool {
+    Arc::strong_count(&self.buffer) == 1
+  }
+
+  pub fn capacity(&self) -> usize {
+    SIZE
+  }
+}
+
+impl<T: Copy, const SIZE: usize> Consumer<T, SIZE> {
+  pub fn pop_slice(&mut self, values: &mut [T]) -> usize {
+    let head = self.buffer.head.0.load(AtomicOrdering::Relaxed);
+    let tail = self.buffer.tail.0.load(AtomicOrdering::Acquire);
+    let count = values.len().min(SpscBuffer::<T, SIZE>::distance(head, tail));
+
+    for (offset, value) in values[..count].iter_mut().enumerate() {
+      *value = unsafe { (*self.buffer.slot(SpscBuffer::<T, SIZE>::advance(head, offset))).assume_init_read() };
+    }
+
+    self.buffer.head.0.store(SpscBuffer::<T, SIZE>::advance(head, count), AtomicOrdering::Release);
+
+    count
+  }
+}
+
+pub trait Clock {
+  fn now(&self) -> Instant;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now(&self) -> Instant {
+    Instant::now()
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+  now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+  pub fn new() -> Self {
+    Self::starting_at(Instant::now())
+  }
+
+  pub fn starting_at(now: Instant) -> Self {
+    Self { now: Arc::new(Mutex::new(now)) }
+  }
+
+  pub fn advance(&self, duration: Duration) {
+    *self.now.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+  }
+
+  pub fn set(&self, now: Instant) {
+    *self.now.lock().unwrap_or_else(PoisonError::into_inner) = now;
+  }
+}
+
+impl Default for ManualClock {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Clock for ManualClock {
+  fn now(&self) -> Instant {
+    *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Expired<T> {
+  pub key: Handle,
+  pub value: T,
+  pub deadline: Instant,
+}
+
+pub struct DelayQueue<T, C: Clock = SystemClock> {
+  entries: IndexedPriorityQueue<T, (Instant, u64), MinFirst>,
+  clock: C,
+  sequence: u64,
+}
+
+impl<T> DelayQueue<T> {
+  pub fn new() -> Self {
+    Self::with_clock(SystemClock)
+  }
+}
+
+impl<T, C: Clock> DelayQueue<T, C> {
+  pub fn with_clock(clock: C) -> Self {
+    Self {
+      entries: IndexedPriorityQueue::with_comparator(MinFirst),
+      clock,
+      sequence: 0,
+    }
+  }
+
+  pub fn clock(&self) -> &C {
+    &self.clock
+  }
+
+  pub fn insert_at(&mut self, value: T, deadline: Instant) -> Handle {
+    let priority = self.next_priority(deadline);
+
+    self.entries.enqueue(value, priority)
+  }
+
+  pub fn insert(&mut self, value: T, delay: Duration) -> Handle {
+    let deadline = self.clock.now() + delay;
+
+    self.insert_at(value, deadline)
+  }
+
+  pub fn reset(&mut self, key: Handle, deadline: Instant) -> Result<(), Error> {
+    if !self.entries.contains(key) {
+      return Err(Error::InvalidHandle);
+    }
+
+    let priority = self.next_priority(deadline);
+
+    self.entries.change_priority(key, priority)?;
+
+    Ok(())
+  }
+
+  pub fn cancel(&mut self, key: Handle) -> Result<T, Error> {
+    self.entries.remove(key).map(|(value, _)| value)
+  }
+
+  pub fn poll_expired(&mut self, now: Instant) -> Option<Expired<T>> {
+    let deadline = self.next_deadline()?;
+
+    if deadline > now {
+      return None;
+    }
+
+    let key = self.entries.peek_handle().ok()?;
+    let (value, (deadline, _)) = self.entries.remove(key).ok()?;
+
+    Some(Expired { key, value, deadline })
+  }
+
+  pub fn poll(&mut self) -> Option<Expired<T>> {
+    let now = self.clock.now();
+
+    self.poll_expired(now)
+  }
+
+  pub fn drain_expired(&mut self, now: Instant) -> Vec<Expired<T>> {
+    let mut expired = Vec::new();
+
+    while let Some(entry) = self.poll_expired(now) {
+      expired.push(entry);
+    }
+
+    expired
+  }
+
+  pub fn next_deadline(&self) -> Option<Instant> {
+    self.entries.peek().ok().map(|(_, (deadline, _))| *deadline)
+  }
+
+  pub fn deadline(&self, key: Handle) -> Result<Instant, Error> {
+    self.entries.get(key).map(|(_, (deadline, _))| *deadline)
+  }
+
+  pub fn get(&self, key: Handle) -> Result<&T, Error> {
+    self.entries.get(key).map(|(value, _)| value)
+  }
+
+  pub fn contains(&self, key: Handle) -> bool {
+    self.entries.contains(key)
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.entries.clear();
+  }
+
+  fn next_priority(&mut self, deadline: Instant) -> (Instant, u64) {
+    self.sequence += 1;
+
+    (deadline, self.sequence)
+  }
+}
+
+impl<T> Default for DelayQueue<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+pub struct HeapPriorityQueue {
+  heap: Heap<i32>,
+}
+
+impl HeapPriorityQueue {
+  pub fn new() -> Self {
+    Self { heap: Heap::new() }
+  }
+
+  pub fn enqueue(&mut self, value: i32) {
+    self.heap.insert(value);
+  }
+
+  pub fn dequeue(&mut self) -> Result<i32, Error> {
+    self.heap.remove()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.heap.is_empty()
+  }
+}
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn array_queue_push_respects_mode() {
+    let mut rejecting = ArrayQueue::<i32, 2>::new();
+
+    assert_eq!(rejecting.push(1), Ok(None));
+    assert_eq!(rejecting.push(2), Ok(None));
+    assert_eq!(rejecting.push(3), Err(Error::CapacityExceeded { capacity: 2 }));
+    assert_eq!(rejecting.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+    let mut overwriting = ArrayQueue::<i32, 2>::with_mode(OverflowMode::Overwrite);
+
+    assert_eq!(overwriting.push(1), Ok(None));
+    assert_eq!(overwriting.push(2), Ok(None));
+    assert_eq!(overwriting.push(3), Ok(Some(1)));
+    assert_eq!(overwriting.push(4), Ok(Some(2)));
+    assert_eq!(overwriting.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+
+    let mut empty = ArrayQueue::<i32, 0>::with_mode(OverflowMode::Overwrite);
+
+    assert_eq!(empty.push(1), Ok(Some(1)));
+    assert!(empty.is_empty());
+  }
+
+  #[test]
+  fn make_contiguous_unwraps_the_ring() {
+    let mut deque = Deque::with_capacity(4);
+
+    deque.extend([3, 4]);
+    deque.push_front(2);
+    deque.push_front(1);
+    deque.pop_back().unwrap();
+    deque.push_back(5);
+
+    assert_ne!(deque.as_slices().1, &[] as &[i32]);
+    assert_eq!(deque.make_contiguous(), &mut [1, 2, 3, 5]);
+    assert_eq!(deque.as_slices(), (&[1, 2, 3, 5][..], &[][..]));
+  }
+
+  #[test]
+  fn make_contiguous_handles_zero_sized_values() {
+    let mut deque = Deque::new();
+
+    deque.push_front(());
+    deque.push_back(());
+    deque.push_front(());
+
+    assert_eq!(deque.make_contiguous().len(), 3);
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.pop_back(), Ok(()));
+  }
+
+  #[test]
+  fn spsc_slices_cross_the_wrap_point() {
+    let (mut producer, mut consumer) = spsc::<u32, 5>();
+    let mut next_pushed = 0;
+    let mut next_popped = 0;
+
+    for round in 0..20 {
+      let batch = (next_pushed..next_pushed + 4).collect::<Vec<_>>();
+
+      assert_eq!(producer.push_slice(&batch), 4);
+      next_pushed += 4;
+
+      assert_eq!(producer.push_slice(&[next_pushed, next_pushed + 1]), 1);
+      next_pushed += 1;
+      assert!(producer.is_full());
+
+      let mut popped = [0; 3];
+      assert_eq!(consumer.pop_slice(&mut popped), 3);
+      assert_eq!(popped, [next_popped, next_popped + 1, next_popped + 2]);
+      next_popped += 3;
+
+      let mut rest = [0; 8];
+      let count = consumer.pop_slice(&mut rest);
+      assert_eq!(count, 2, "round {}", round);
+      assert_eq!(&rest[..count], &[next_popped, next_popped + 1]);
+      next_popped += 2;
+
+      assert!(consumer.is_empty());
+      assert_eq!(producer.push(next_pushed).map_err(|error| error.value), Ok(()));
+      assert_eq!(consumer.pop(), Ok(next_pushed));
+      next_pushed += 1;
+      next_popped += 1;
+    }
+
+    assert_eq!(consumer.pop_slice(&mut [0; 4]), 0);
+    assert_eq!(producer.push_slice(&[]), 0);
+  }
+
+  #[test]
+  fn spsc_delivers_in_order_across_threads() {
+    const COUNT: u64 = 200_000;
+
+    let (mut producer, mut consumer) = spsc::<u64, 64>();
+
+    let producing = std::thread::spawn(move || {
+      let mut next = 0;
+      let mut batch = [0; 7];
+
+      while next < COUNT {
+        let pushed = if next % 3 == 0 {
+          let length = (COUNT - next).min(batch.len() as u64) as usize;
+
+          for (offset, value) in batch[..length].iter_mut().enumerate() {
+            *value = next + offset as u64;
+          }
+
+          producer.push_slice(&batch[..length]) as u64
+        } else {
+          u64::from(producer.push(next).is_ok())
+        };
+
+        if pushed == 0 {
+          std::thread::yield_now();
+        }
+
+        next += pushed;
+      }
+    });
+
+    let mut expected = 0;
+    let mut buffer = [0; 5];
+
+    while expected < COUNT {
+      let popped = if expected % 2 == 0 {
+        consumer.pop_slice(&mut buffer)
+      } else {
+        match consumer.pop() {
+          Ok(value) => {
+            buffer[0] = value;
+
+            1
+          }
+          Err(_) => 0,
+        }
+      };
+
+      if popped == 0 {
+        std::thread::yield_now();
+      }
+
+      for value in &buffer[..popped] {
+        assert_eq!(*value, expected);
+        expected += 1;
+      }
+    }
+
+    producing.join().unwrap();
+
+    assert!(consumer.is_empty());
+    assert!(consumer.is_abandoned());
+  }
+}
@@ -1,41 +1,633 @@
-use crate::lists::LinkedList;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::Rev;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::{ptr, slice};
 
-pub struct Stack<T: Clone> {
-  items: LinkedList<T>
+use crate::allocators::Allocator;
+use crate::arrays::{DynamicArray, GrowthPolicy};
+use crate::errors::Error;
+use crate::lists::{self, LinkedList};
+
+pub trait StackStorage<T> {
+  type Iter<'a>: Iterator<Item = &'a T> where Self: 'a, T: 'a;
+
+  fn push(&mut self, value: T) -> Result<(), Error>;
+  fn pop(&mut self) -> Option<T>;
+  fn pop_bottom(&mut self) -> Option<T>;
+  fn peek(&self) -> Option<&T>;
+  fn peek_mut(&mut self) -> Option<&mut T>;
+  fn len(&self) -> usize;
+  fn iter(&self) -> Self::Iter<'_>;
+  fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+  fn is_full(&self) -> bool {
+    false
+  }
 }
 
-impl<T: Clone + PartialEq> Stack<T> {
+impl<T> StackStorage<T> for LinkedList<T> {
+  type Iter<'a> = lists::Iter<'a, T> where T: 'a;
+
+  fn push(&mut self, value: T) -> Result<(), Error> {
+    self.add_first(value);
+
+    Ok(())
+  }
+
+  fn pop(&mut self) -> Option<T> {
+    self.remove_first().ok()
+  }
+
+  fn pop_bottom(&mut self) -> Option<T> {
+    self.remove_last().ok()
+  }
+
+  fn peek(&self) -> Option<&T> {
+    self.peek_front()
+  }
+
+  fn peek_mut(&mut self) -> Option<&mut T> {
+    self.get_mut(0)
+  }
+
+  fn len(&self) -> usize {
+    self.size()
+  }
+
+  fn iter(&self) -> Self::Iter<'_> {
+    LinkedList::iter(self)
+  }
+}
+
+impl<T, P: GrowthPolicy, A: Allocator> StackStorage<T> for DynamicArray<T, P, A> {
+  type Iter<'a> = Rev<slice::Iter<'a, T>> where Self: 'a, T: 'a;
+
+  fn push(&mut self, value: T) -> Result<(), Error> {
+    self.insert(value);
+
+    Ok(())
+  }
+
+  fn pop(&mut self) -> Option<T> {
+    DynamicArray::pop(self)
+  }
+
+  fn pop_bottom(&mut self) -> Option<T> {
+    self.remove_at(0).ok()
+  }
+
+  fn peek(&self) -> Option<&T> {
+    self.as_slice().last()
+  }
+
+  fn peek_mut(&mut self) -> Option<&mut T> {
+    self.as_mut_slice().last_mut()
+  }
+
+  fn len(&self) -> usize {
+    DynamicArray::len(self)
+  }
+
+  fn iter(&self) -> Self::Iter<'_> {
+    self.as_slice().iter().rev()
+  }
+}
+
+pub struct FixedArray<T, const SIZE: usize> {
+  items: [MaybeUninit<T>; SIZE],
+  length: usize,
+}
+
+impl<T, const SIZE: usize> FixedArray<T, SIZE> {
   pub fn new() -> Self {
     Self {
-      items: LinkedList::<T>::new()
+      items: [const { MaybeUninit::uninit() }; SIZE],
+      length: 0,
     }
   }
 
-  pub fn push(&mut self, value: T) {
-    self.items.add_first(value);
+  pub fn as_slice(&self) -> &[T] {
+    unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.length) }
+  }
+
+  pub fn as_mut_slice(&mut self) -> &mut [T] {
+    unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.length) }
   }
+}
 
-  pub fn peek(&self) -> Result<T, &str> {
-    if self.is_empty() {
-      return Err("Stack is empty");
+impl<T, const SIZE: usize> StackStorage<T> for FixedArray<T, SIZE> {
+  type Iter<'a> = Rev<slice::Iter<'a, T>> where T: 'a;
+
+  fn push(&mut self, value: T) -> Result<(), Error> {
+    if self.is_full() {
+      return Err(Error::CapacityExceeded { capacity: SIZE });
+    }
+
+    self.items[self.length].write(value);
+    self.length += 1;
+
+    Ok(())
+  }
+
+  fn pop(&mut self) -> Option<T> {
+    if self.length == 0 {
+      return None;
     }
-    
-    self.items.get_kth_from_end(self.items.size())
+
+    self.length -= 1;
+
+    Some(unsafe { self.items[self.length].assume_init_read() })
   }
 
-  pub fn pop(&mut self) -> Result<T, &str> {
-    if self.is_empty() {
-      return Err("Stack is empty");
+  fn pop_bottom(&mut self) -> Option<T> {
+    if self.length == 0 {
+      return None;
     }
 
-    let item_to_pop = self.items.get_kth_from_end(self.items.size())?;
+    self.length -= 1;
 
-    self.items.remove_first()?;
+    unsafe {
+      let items = self.items.as_mut_ptr() as *mut T;
+      let value = ptr::read(items);
 
-    Ok(item_to_pop)
+      ptr::copy(items.add(1), items, self.length);
+
+      Some(value)
+    }
+  }
+
+  fn peek(&self) -> Option<&T> {
+    self.as_slice().last()
+  }
+
+  fn peek_mut(&mut self) -> Option<&mut T> {
+    self.as_mut_slice().last_mut()
+  }
+
+  fn len(&self) -> usize {
+    self.length
+  }
+
+  fn iter(&self) -> Self::Iter<'_> {
+    self.as_slice().iter().rev()
+  }
+
+  fn is_full(&self) -> bool {
+    self.length == SIZE
+  }
+}
+
+impl<T, const SIZE: usize> Default for FixedArray<T, SIZE> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, const SIZE: usize> Drop for FixedArray<T, SIZE> {
+  fn drop(&mut self) {
+    unsafe {
+      ptr::drop_in_place(self.as_mut_slice());
+    }
+  }
+}
+
+pub struct Stack<T, S: StackStorage<T> = LinkedList<T>> {
+  items: S,
+  marker: PhantomData<T>,
+}
+
+pub type ArrayStack<T> = Stack<T, DynamicArray<T>>;
+
+pub type FixedStack<T, const SIZE: usize> = Stack<T, FixedArray<T, SIZE>>;
+
+impl<T> Stack<T> {
+  pub fn new() -> Self {
+    Self::with_storage(LinkedList::new())
+  }
+}
+
+impl<T> Stack<T, DynamicArray<T>> {
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self::with_storage(DynamicArray::new(capacity))
+  }
+}
+
+impl<T, const SIZE: usize> Stack<T, FixedArray<T, SIZE>> {
+  pub fn fixed() -> Self {
+    Self::with_storage(FixedArray::new())
+  }
+}
+
+impl<T, S: StackStorage<T>> Stack<T, S> {
+  pub fn with_storage(storage: S) -> Self {
+    Self {
+      items: storage,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn push(&mut self, value: T) -> Result<(), Error> {
+    self.items.push(value)
+  }
+
+  pub fn pop(&mut self) -> Result<T, Error> {
+    self.items.pop().ok_or(Error::Empty)
+  }
+
+  pub fn pop_bottom(&mut self) -> Result<T, Error> {
+    self.items.pop_bottom().ok_or(Error::Empty)
+  }
+
+  pub fn peek(&self) -> Result<&T, Error> {
+    self.items.peek().ok_or(Error::Empty)
+  }
+
+  pub fn peek_mut(&mut self) -> Result<&mut T, Error> {
+    self.items.peek_mut().ok_or(Error::Empty)
+  }
+
+  pub fn len(&self) -> usize {
+    self.items.len()
   }
 
   pub fn is_empty(&self) -> bool {
     self.items.is_empty()
   }
-}
\ No newline at end of file
+
+  pub fn is_full(&self) -> bool {
+    self.items.is_full()
+  }
+
+  pub fn clear(&mut self) {
+    while self.items.pop().is_some() {}
+  }
+
+  pub fn iter(&self) -> S::Iter<'_> {
+    self.items.iter()
+  }
+
+  pub fn into_storage(self) -> S {
+    self.items
+  }
+}
+
+impl<T, S: StackStorage<T> + Default> Default for Stack<T, S> {
+  fn default() -> Self {
+    Self::with_storage(S::default())
+  }
+}
+
+impl<T: Debug, S: StackStorage<T>> Debug for Stack<T, S> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T: Display, S: StackStorage<T>> Display for Stack<T, S> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let formatted_items = self.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(", ");
+
+    write!(f, "[{}]", formatted_items)?;
+
+    Ok(())
+  }
+}
+
+pub struct MinMaxStack<T> {
+  values: DynamicArray<T>,
+  minimums: ArrayStack<usize>,
+  maximums: ArrayStack<usize>,
+}
+
+impl<T: Ord> MinMaxStack<T> {
+  pub fn new() -> Self {
+    Self {
+      values: DynamicArray::new(0),
+      minimums: Stack::with_capacity(0),
+      maximums: Stack::with_capacity(0),
+    }
+  }
+
+  pub fn push(&mut self, value: T) {
+    let index = self.values.len();
+
+    if !self.minimums.peek().is_ok_and(|&minimum| self.values[minimum] <= value) {
+      self.minimums.push(index).unwrap();
+    }
+
+    if !self.maximums.peek().is_ok_and(|&maximum| self.values[maximum] >= value) {
+      self.maximums.push(index).unwrap();
+    }
+
+    self.values.insert(value);
+  }
+
+  pub fn pop(&mut self) -> Result<T, Error> {
+    let value = self.values.pop().ok_or(Error::Empty)?;
+    let index = self.values.len();
+
+    if self.minimums.peek() == Ok(&index) {
+      self.minimums.pop()?;
+    }
+
+    if self.maximums.peek() == Ok(&index) {
+      self.maximums.pop()?;
+    }
+
+    Ok(value)
+  }
+
+  pub fn peek(&self) -> Result<&T, Error> {
+    self.values.as_slice().last().ok_or(Error::Empty)
+  }
+
+  pub fn min(&self) -> Result<&T, Error> {
+    self.minimums.peek().map(|&index| &self.values[index])
+  }
+
+  pub fn max(&self) -> Result<&T, Error> {
+    self.maximums.peek().map(|&index| &self.values[index])
+  }
+
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.values.clear();
+    self.minimums.clear();
+    self.maximums.clear();
+  }
+
+  pub fn iter(&self) -> Rev<slice::Iter<'_, T>> {
+    self.values.as_slice().iter().rev()
+  }
+}
+
+impl<T: Ord> Default for MinMaxStack<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Debug> Debug for MinMaxStack<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_list().entries(self.values.as_slice().iter().rev()).finish()
+  }
+}
+
+pub struct MonotonicStack<'a, T> {
+  items: &'a [T],
+}
+
+impl<'a, T: Ord> MonotonicStack<'a, T> {
+  pub fn new(items: &'a [T]) -> Self {
+    Self { items }
+  }
+
+  pub fn next_greater(&self) -> Vec<Option<usize>> {
+    self.scan_next(|current, pending| current > pending)
+  }
+
+  pub fn next_smaller(&self) -> Vec<Option<usize>> {
+    self.scan_next(|current, pending| current < pending)
+  }
+
+  pub fn previous_greater(&self) -> Vec<Option<usize>> {
+    self.scan_previous(|current, candidate| candidate > current)
+  }
+
+  pub fn previous_smaller(&self) -> Vec<Option<usize>> {
+    self.scan_previous(|current, candidate| candidate < current)
+  }
+
+  fn scan_next<F>(&self, resolves: F) -> Vec<Option<usize>> where F: Fn(&T, &T) -> bool {
+    let mut result = vec![None; self.items.len()];
+    let mut pending: ArrayStack<usize> = Stack::with_capacity(self.items.len());
+
+    for (index, current) in self.items.iter().enumerate() {
+      while let Ok(&top) = pending.peek() {
+        if !resolves(current, &self.items[top]) {
+          break;
+        }
+
+        result[top] = Some(index);
+        pending.pop().unwrap();
+      }
+
+      pending.push(index).unwrap();
+    }
+
+    result
+  }
+
+  fn scan_previous<F>(&self, is_kept: F) -> Vec<Option<usize>> where F: Fn(&T, &T) -> bool {
+    let mut result = vec![None; self.items.len()];
+    let mut candidates: ArrayStack<usize> = Stack::with_capacity(self.items.len());
+
+    for (index, current) in self.items.iter().enumerate() {
+      while let Ok(&top) = candidates.peek() {
+        if is_kept(current, &self.items[top]) {
+          break;
+        }
+
+        candidates.pop().unwrap();
+      }
+
+      result[index] = candidates.peek().ok().copied();
+      candidates.push(index).unwrap();
+    }
+
+    result
+  }
+}
+
+enum Entry<A> {
+  Action(A),
+  Checkpoint,
+}
+
+pub struct History<A> {
+  undo: Stack<Entry<A>>,
+  redo: Stack<Entry<A>>,
+  undo_actions: usize,
+  redo_actions: usize,
+  limit: Option<usize>,
+}
+
+impl<A> History<A> {
+  pub fn new() -> Self {
+    Self {
+      undo: Stack::new(),
+      redo: Stack::new(),
+      undo_actions: 0,
+      redo_actions: 0,
+      limit: None,
+    }
+  }
+
+  pub fn with_limit(limit: usize) -> Self {
+    Self { limit: Some(limit), ..Self::new() }
+  }
+
+  pub fn limit(&self) -> Option<usize> {
+    self.limit
+  }
+
+  pub fn record(&mut self, action: A) {
+    self.redo.clear();
+    self.redo_actions = 0;
+
+    self.undo.push(Entry::Action(action)).unwrap();
+    self.undo_actions += 1;
+
+    self.enforce_limit();
+  }
+
+  pub fn checkpoint(&mut self) {
+    if let Ok(Entry::Action(_)) = self.undo.peek() {
+      self.undo.push(Entry::Checkpoint).unwrap();
+    }
+  }
+
+  pub fn undo(&mut self) -> Option<&A> {
+    if self.undo_actions == 0 {
+      return None;
+    }
+
+    while let Ok(Entry::Checkpoint) = self.undo.peek() {
+      Self::transfer(&mut self.undo, &mut self.redo);
+    }
+
+    Self::transfer(&mut self.undo, &mut self.redo);
+    self.undo_actions -= 1;
+    self.redo_actions += 1;
+
+    Self::top_action(&self.redo)
+  }
+
+  pub fn redo(&mut self) -> Option<&A> {
+    if self.redo_actions == 0 {
+      return None;
+    }
+
+    while let Ok(Entry::Checkpoint) = self.redo.peek() {
+      Self::transfer(&mut self.redo, &mut self.undo);
+    }
+
+    Self::transfer(&mut self.redo, &mut self.undo);
+    self.redo_actions -= 1;
+    self.undo_actions += 1;
+
+    Self::top_action(&self.undo)
+  }
+
+  pub fn undo_to_checkpoint(&mut self) -> Vec<&A> {
+    let count = Self::transfer_group(&mut self.undo, &mut self.redo);
+
+    self.undo_actions -= count;
+    self.redo_actions += count;
+
+    Self::group_actions(&self.redo, count)
+  }
+
+  pub fn redo_to_checkpoint(&mut self) -> Vec<&A> {
+    let count = Self::transfer_group(&mut self.redo, &mut self.undo);
+
+    self.redo_actions -= count;
+    self.undo_actions += count;
+
+    Self::group_actions(&self.undo, count)
+  }
+
+  pub fn can_undo(&self) -> bool {
+    self.undo_actions > 0
+  }
+
+  pub fn can_redo(&self) -> bool {
+    self.redo_actions > 0
+  }
+
+  pub fn undo_len(&self) -> usize {
+    self.undo_actions
+  }
+
+  pub fn redo_len(&self) -> usize {
+    self.redo_actions
+  }
+
+  pub fn clear(&mut self) {
+    self.undo.clear();
+    self.redo.clear();
+    self.undo_actions = 0;
+    self.redo_actions = 0;
+  }
+
+  fn enforce_limit(&mut self) {
+    let Some(limit) = self.limit else {
+      return;
+    };
+
+    while self.undo_actions > limit {
+      if let Ok(Entry::Action(_)) = self.undo.pop_bottom() {
+        self.undo_actions -= 1;
+      }
+    }
+
+    while let Some(Entry::Checkpoint) = self.undo.iter().next_back() {
+      self.undo.pop_bottom().unwrap();
+    }
+  }
+
+  fn transfer(from: &mut Stack<Entry<A>>, to: &mut Stack<Entry<A>>) {
+    if let Ok(entry) = from.pop() {
+      to.push(entry).unwrap();
+    }
+  }
+
+  fn transfer_group(from: &mut Stack<Entry<A>>, to: &mut Stack<Entry<A>>) -> usize {
+    while let Ok(Entry::Checkpoint) = from.peek() {
+      Self::transfer(from, to);
+    }
+
+    let mut count = 0;
+
+    while let Ok(Entry::Action(_)) = from.peek() {
+      Self::transfer(from, to);
+
+      count += 1;
+    }
+
+    count
+  }
+
+  fn top_action(stack: &Stack<Entry<A>>) -> Option<&A> {
+    match stack.peek() {
+      Ok(Entry::Action(action)) => Some(action),
+      _ => None,
+    }
+  }
+
+  fn group_actions(stack: &Stack<Entry<A>>, count: usize) -> Vec<&A> {
+    let mut actions = stack.iter().take(count).filter_map(|entry| match entry {
+      Entry::Action(action) => Some(action),
+      Entry::Checkpoint => None,
+    }).collect::<Vec<_>>();
+
+    actions.reverse();
+
+    actions
+  }
+}
+
+impl<A> Default for History<A> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -1,5 +1,9 @@
 use std::{collections::{HashMap, BinaryHeap, HashSet}, hash::Hash, fmt::Display, cmp::{Ordering, Reverse}};
 
+use crate::errors::Error;
+use crate::heaps::MinFirst;
+use crate::queues::IndexedPriorityQueue;
+
 #[derive(Eq, PartialEq)]
 struct Edge<'a, T> {
   from: &'a T,
@@ -107,13 +111,13 @@ impl<'a, T: Eq + Hash + Ord> WeightedGraph<'a, T> {
     self.nodes.insert(value, Node::new(value));
   }
 
-  pub fn add_edge(&mut self, from: &'a T, to: &'a T, weight: usize) -> Result<(), &'static str>{
+  pub fn add_edge(&mut self, from: &'a T, to: &'a T, weight: usize) -> Result<(), Error> {
     if !self.is_node_exists(from) {
-      return Err("From node is not exist");
+      return Err(Error::NodeMissing);
     }
 
     if !self.is_node_exists(to) {
-      return Err("To node is not exist");
+      return Err(Error::NodeMissing);
     }
 
     self.nodes.get_mut(from).unwrap().add_edge(to, weight);
@@ -122,56 +126,55 @@ impl<'a, T: Eq + Hash + Ord> WeightedGraph<'a, T> {
     Ok(())
   }
 
-  pub fn shortest_path(&self, from: &'a T, to: &'a T) -> Result<Vec<&'a T>, &'static str> {
+  pub fn shortest_path(&self, from: &'a T, to: &'a T) -> Result<Vec<&'a T>, Error> {
     let from_node = self.nodes.get(from);
 
     if from_node.is_none() {
-      return Err("From node is not exist");
+      return Err(Error::NodeMissing);
     }
 
     let to_node = self.nodes.get(to);
 
     if to_node.is_none() {
-      return Err("To node is not exist");
+      return Err(Error::NodeMissing);
     }
 
-    let mut priority_queue = BinaryHeap::new();
+    let mut priority_queue = IndexedPriorityQueue::with_comparator(MinFirst);
+    let mut handles = HashMap::new();
     let mut distances = HashMap::new();
     let mut previous_nodes = HashMap::new(); 
 
-    priority_queue.push(Reverse(from_node.unwrap()));
+    handles.insert(from, priority_queue.enqueue(from, 0));
     distances.insert(from, 0);
 
     let mut visited_nodes = HashSet::with_capacity(self.nodes.len());
 
-    while priority_queue.len() > 0 {
-      let node_entry = priority_queue.pop();
+    while let Ok((value, distance)) = priority_queue.dequeue() {
+      visited_nodes.insert(value);
 
-      if node_entry.is_none() {
-        continue;
-      }
-
-      let node = node_entry.unwrap().0;
-
-      for (current, edge) in &node.edges {
+      for (current, edge) in &self.nodes[value].edges {
         if visited_nodes.contains(current) {
           continue;
         }
 
-        let proposed_distance = edge.weight + distances.get(node.value).or(Some(&0)).unwrap();
-        let current_distance = distances.entry(current).or_insert(proposed_distance);
-    
-        if proposed_distance > *current_distance {
+        let proposed_distance = distance + edge.weight;
+
+        if distances.get(current).is_some_and(|&current_distance| proposed_distance >= current_distance) {
           continue;
         }
-    
-        *current_distance = proposed_distance;
-        
-        previous_nodes.insert(current, node.value);
-        priority_queue.push(Reverse(&self.nodes[current]));
-      }
 
-      visited_nodes.insert(node.value);
+        distances.insert(current, proposed_distance);
+        previous_nodes.insert(current, value);
+
+        match handles.get(current) {
+          Some(&handle) if priority_queue.contains(handle) => {
+            priority_queue.change_priority(handle, proposed_distance)?;
+          }
+          _ => {
+            handles.insert(current, priority_queue.enqueue(current, proposed_distance));
+          }
+        }
+      }
     }
 
     Ok(self.construct_path(to_node, previous_nodes))
@@ -193,7 +196,7 @@ impl<'a, T: Eq + Hash + Ord> WeightedGraph<'a, T> {
     false
   }
 
-  pub fn minimum_spanning_tree(&self) -> Result<Self, &'static str> {
+  pub fn minimum_spanning_tree(&self) -> Result<Self, Error> {
     let mut spanning_tree = Self::new();
    
     if self.is_empty() {